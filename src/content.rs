@@ -1,8 +1,18 @@
+pub mod color;
 mod content_style;
+pub mod text;
 
 use colored::Colorize;
+use color::Layer;
+use super::terminal::color_depth;
 pub use super::content_iterator::{ContentIterator};
-pub use content_style::{ContentStyle, Alignment, Wrap, CellWidth};
+pub use content_style::{
+    ContentStyle, Alignment, Wrap, CellWidth, StyleParseError,
+    StyleParseErrorKind, TextAttributes
+};
+
+/// Marks the end of truncated content.
+const ELLIPSIS: &str = "...";
 
 /// Represents a line of content.
 #[derive(Debug, Clone)]
@@ -36,6 +46,17 @@ impl Content {
         }
     }
 
+    /// Returns the unformatted text of the content.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The content.
+    pub fn text(
+        self: &Content
+    ) -> &str {
+        &self.content
+    }
+
     /// Returns an iterator for the line parts of a content.
     ///
     /// # Arguments
@@ -48,61 +69,65 @@ impl Content {
         base_style: &ContentStyle,
         width: usize
     ) -> ContentIterator {
-        let content_len = self.content.len();
+        let style = self.resolve_style(base_style);
 
-        let mut result: Vec<String> = Vec::new();
+        let result: Vec<String> =
+            self.split_lines(style, width).iter()
+                .map(|line| Content::format(line, style, width))
+                .collect();
 
-        // Use base style if needed
-        let style = match &self.style {
-            Some(style) => style,
-            None => &base_style
-        };
+        ContentIterator::new(result)
+    }
 
+    /// Splits content into the unformatted line parts that fit a width.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The content to split.
+    /// * `style` - The style describing how to wrap or truncate.
+    /// * `width` - The width at which to wrap or truncate.
+    fn split_lines(
+        self: &Content,
+        style: &ContentStyle,
+        width: usize
+    ) -> Vec<String> {
         match style.wrap {
             // Truncate on single line
             Wrap::Truncate => {
-                // Pad or truncate
-                if content_len <= width {
-                    result.push(
-                        Content::format(
-                            &self.content,
-                            &style,
-                            width
-                        )
-                    );
+                // Pad or truncate (marking the cut with an ellipsis if the
+                //  width leaves room for some content as well)
+                if self.measure_width() <= width {
+                    vec!(self.content.clone())
+                } else if width > ELLIPSIS.len() {
+                    vec!(text::truncate_to_width(
+                        &self.content,
+                        width - ELLIPSIS.len()
+                    ) + ELLIPSIS)
                 } else {
-                    result.push(
-                        Content::format(
-                            &self.content[0..(width - 3)],
-                            &style,
-                            width
-                        )
-                    );
+                    vec!(text::truncate_to_width(&self.content, width))
                 }
             },
             // Wrap to multiple lines
-            Wrap::Wrap => {
-                let num_lines = self.measure_height(width);
-                let partial_line_len = content_len.rem_euclid(width);
-
-                // Collect the line parts
-                for line_ix in 0..num_lines {
-                    let from = line_ix * width;
-                    let to =
-                        if line_ix < num_lines - 1 { from + width }
-                        else { from + partial_line_len };
-
-                    result.push(
-                        Content::format(
-                            &self.content[from..to],
-                            &style,
-                            width)
-                    );
-                }
-            },
+            Wrap::Wrap => text::split_into_width(&self.content, width),
+            // Wrap to multiple lines at word boundaries
+            Wrap::Word => text::wrap_words(&self.content, width),
         }
+    }
 
-        ContentIterator::new(result)
+    /// Returns the style of this content, or the base style if it has none.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The content being styled.
+    /// * `base_style` - The base style to use if no content style is defined.
+    pub fn resolve_style<'a>(
+        self: &'a Content,
+        base_style: &'a ContentStyle
+    ) -> &'a ContentStyle {
+        match &self.style {
+            Some(style) => style,
+            None => base_style
+        }
     }
 
     fn format(
@@ -113,12 +138,25 @@ impl Content {
         let mut result = Content::pad(line, &style.alignment, width);
 
         // Apply colors
-        if style.foreground_color != None {
-            result = result.color(style.foreground_color.unwrap()).to_string();
+        let depth = color_depth();
+        if let Some(color) = style.foreground_color {
+            result = color::paint(&result, color, Layer::Foreground, depth);
         }
-        if style.background_color != None {
-            result =
-                result.on_color(style.background_color.unwrap()).to_string();
+        if let Some(color) = style.background_color {
+            result = color::paint(&result, color, Layer::Background, depth);
+        }
+
+        // Apply text attributes
+        let attributes = &style.attributes;
+        if !attributes.is_empty() {
+            let mut styled = result.normal();
+            if attributes.bold { styled = styled.bold(); }
+            if attributes.italic { styled = styled.italic(); }
+            if attributes.underline { styled = styled.underline(); }
+            if attributes.dimmed { styled = styled.dimmed(); }
+            if attributes.strikethrough { styled = styled.strikethrough(); }
+            if attributes.reversed { styled = styled.reversed(); }
+            result = styled.to_string();
         }
 
         result
@@ -129,12 +167,12 @@ impl Content {
         alignment: &Alignment,
         width: usize
     ) -> String {
-        let content_len = line.len();
+        let content_width = text::display_width(line);
 
-        if content_len == width {
+        if content_width >= width {
             line.to_string()
         } else {
-            let padding = width - content_len;
+            let padding = width - content_width;
 
             match alignment {
                 Alignment::Left => {
@@ -172,7 +210,7 @@ impl Content {
         }
     }
 
-    /// Measures the width of content.
+    /// Measures the display width of content, in terminal columns.
     ///
     /// # Arguments
     ///
@@ -180,41 +218,26 @@ impl Content {
     pub fn measure_width(
         self: &Content
     ) -> usize {
-        self.content.len()
+        text::display_width(&self.content)
     }
 
     /// Measures the height of this content if formatted to a specific width.
     ///
     /// This is useful for determining if content will use additional height
-    ///  when wrapped.
+    ///  when wrapped. The height always matches the number of lines returned
+    ///  by `get_iterator` for the same base style and width.
     ///
     /// # Arguments
     ///
     /// * `self` - The content being measured.
+    /// * `base_style` - The base style to use if no content style is defined.
     /// * `width` - The width the content is being measured into.
     pub fn measure_height(
         self: &Content,
+        base_style: &ContentStyle,
         width: usize
     ) -> usize {
-        // Calculate height if content will wrap
-        if self.will_wrap() {
-            let content_len = self.content.len();
-
-            // Calculate number of whole lines needed
-            let mut height = content_len.div_euclid(width);
-
-            // Check if there is a final partial line
-            let partial_line_len = content_len.rem_euclid(width);
-            if partial_line_len != 0 {
-                height += 1;
-            }
-
-            height
-        }
-        else {
-            // Content will be truncated (always height 1)
-            1
-        }
+        self.split_lines(self.resolve_style(base_style), width).len()
     }
 
     /// Returns a flag indicating whether this content will wrap.
@@ -228,7 +251,7 @@ impl Content {
         match &self.style {
             Some(style) => {
                 match style.wrap {
-                    Wrap::Wrap => true,
+                    Wrap::Wrap | Wrap::Word => true,
                     Wrap::Truncate => false
                 }
             },
@@ -255,6 +278,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_text_attributes() {
+        let output = Content::format("Bold", &content_style!("{*_}"), 6);
+
+        let expected =
+            if colored::control::SHOULD_COLORIZE.should_colorize() {
+                "\u{1b}[1;4mBold  \u{1b}[0m"
+            } else {
+                "Bold  "
+            };
+
+        assert_eq!(output, expected);
+    }
+
     #[test]
     fn test_pad() {
         let expected = "Test  ";
@@ -267,4 +304,88 @@ mod tests {
             expected
         );
     }
+
+    #[test]
+    fn test_pad_wide_characters() {
+        let expected = " 日本 ";
+
+        assert_eq!(
+            Content::pad(
+                "日本",
+                &Alignment::Center,
+                6),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_wrap_wide_characters() {
+        let content = Content::new(
+            "日本語テキスト".to_string(),
+            Some(content_style!("{;}"))
+        );
+
+        assert_eq!(content.measure_width(), 14);
+        assert_eq!(
+            content.get_iterator(&ContentStyle::default(), 5)
+                .collect::<Vec<String>>(),
+            vec!("日本 ", "語テ ", "キス ", "ト   ")
+        );
+    }
+
+    #[test]
+    fn test_truncate_preserves_escape_sequences() {
+        let content = Content::new(
+            "\u{1b}[31mcolored text\u{1b}[0m".to_string(),
+            None
+        );
+
+        assert_eq!(content.measure_width(), 12);
+        assert_eq!(
+            content.get_iterator(&ContentStyle::default(), 8)
+                .collect::<Vec<String>>(),
+            vec!("\u{1b}[31mcolor\u{1b}[0m...")
+        );
+    }
+
+    #[test]
+    fn test_word_wrap_height_matches_iterator() {
+        let content = Content::new(
+            "Word wrapping keeps words whole".to_string(),
+            Some(content_style!("{~}"))
+        );
+        let lines = content.get_iterator(&ContentStyle::default(), 12)
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            lines,
+            vec!("Word        ", "wrapping    ", "keeps words ", "whole       ")
+        );
+        assert_eq!(
+            content.measure_height(&ContentStyle::default(), 12),
+            lines.len()
+        );
+    }
+
+    #[test]
+    fn test_empty_content_is_one_blank_line() {
+        for style in ["{}", "{;}", "{~}"].iter() {
+            let content = Content::new(
+                String::new(),
+                Some(ContentStyle::from_format(style))
+            );
+
+            assert_eq!(
+                content.get_iterator(&ContentStyle::default(), 4)
+                    .collect::<Vec<String>>(),
+                vec!("    "),
+                "{}",
+                style
+            );
+            assert_eq!(
+                content.measure_height(&ContentStyle::default(), 4),
+                1
+            );
+        }
+    }
 }
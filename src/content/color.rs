@@ -0,0 +1,203 @@
+use colored::{Color, Colorize};
+use crate::terminal::ColorDepth;
+
+/// The levels of each channel in the 6x6x6 color cube of the xterm palette.
+const CUBE_LEVELS: [u8; 6] = [0x00, 0x5f, 0x87, 0xaf, 0xd7, 0xff];
+
+/// The basic colors, in the order of their xterm palette indexes.
+const BASIC_COLORS: [Color; 16] = [
+    Color::Black, Color::Red, Color::Green, Color::Yellow,
+    Color::Blue, Color::Magenta, Color::Cyan, Color::White,
+    Color::BrightBlack, Color::BrightRed, Color::BrightGreen,
+    Color::BrightYellow, Color::BrightBlue, Color::BrightMagenta,
+    Color::BrightCyan, Color::BrightWhite,
+];
+
+/// Whether a color is painted as the text or the background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    Foreground,
+    Background,
+}
+
+/// Returns the red, green and blue values of a color.
+///
+/// Basic colors use the default xterm palette.
+///
+/// # Arguments
+///
+/// * `color` - The color.
+#[must_use]
+pub const fn rgb(color: Color) -> (u8, u8, u8) {
+    match color {
+        Color::Black => (0x00, 0x00, 0x00),
+        Color::Red => (0xcd, 0x00, 0x00),
+        Color::Green => (0x00, 0xcd, 0x00),
+        Color::Yellow => (0xcd, 0xcd, 0x00),
+        Color::Blue => (0x00, 0x00, 0xee),
+        Color::Magenta => (0xcd, 0x00, 0xcd),
+        Color::Cyan => (0x00, 0xcd, 0xcd),
+        Color::White => (0xe5, 0xe5, 0xe5),
+        Color::BrightBlack => (0x7f, 0x7f, 0x7f),
+        Color::BrightRed => (0xff, 0x00, 0x00),
+        Color::BrightGreen => (0x00, 0xff, 0x00),
+        Color::BrightYellow => (0xff, 0xff, 0x00),
+        Color::BrightBlue => (0x5c, 0x5c, 0xff),
+        Color::BrightMagenta => (0xff, 0x00, 0xff),
+        Color::BrightCyan => (0x00, 0xff, 0xff),
+        Color::BrightWhite => (0xff, 0xff, 0xff),
+        Color::TrueColor { r, g, b } => (r, g, b)
+    }
+}
+
+/// Returns the color at an index of the 256-color xterm palette.
+///
+/// The first 16 indexes are the basic colors. The rest (the color cube and
+///  the grayscale ramp) are returned as RGB colors.
+///
+/// # Arguments
+///
+/// * `index` - The palette index.
+#[must_use]
+pub const fn from_index(index: u8) -> Color {
+    if index < 16 {
+        BASIC_COLORS[index as usize]
+    } else if index < 232 {
+        let cube_ix = index - 16;
+        Color::TrueColor {
+            r: CUBE_LEVELS[(cube_ix / 36) as usize],
+            g: CUBE_LEVELS[(cube_ix / 6 % 6) as usize],
+            b: CUBE_LEVELS[(cube_ix % 6) as usize],
+        }
+    } else {
+        let level = 8 + (index - 232) * 10;
+        Color::TrueColor { r: level, g: level, b: level }
+    }
+}
+
+/// Returns the basic color nearest to a color.
+///
+/// # Arguments
+///
+/// * `color` - The color.
+#[must_use]
+pub fn nearest_basic(color: Color) -> Color {
+    BASIC_COLORS.iter()
+        .copied()
+        .min_by_key(|basic| distance(*basic, color))
+        .unwrap_or(Color::White)
+}
+
+/// Returns the index of the color nearest to a color among the 256-color
+///  palette entries that do not depend on the terminal theme (the color
+///  cube and the grayscale ramp).
+///
+/// # Arguments
+///
+/// * `color` - The color.
+#[must_use]
+pub fn nearest_index(color: Color) -> u8 {
+    (16..=255)
+        .min_by_key(|index| distance(from_index(*index), color))
+        .unwrap_or(16)
+}
+
+/// Returns text painted with a color, at a color depth.
+///
+/// Basic colors are painted as they are. RGB colors are painted as 24-bit
+///  colors, as the nearest 256-color palette entry, or as the nearest basic
+///  color, depending on the depth.
+///
+/// # Arguments
+///
+/// * `text` - The text to paint.
+/// * `color` - The color to paint with.
+/// * `layer` - Whether to paint the text or the background.
+/// * `depth` - The color depth of the terminal.
+#[must_use]
+pub fn paint(
+    text: &str,
+    color: Color,
+    layer: Layer,
+    depth: ColorDepth
+) -> String {
+    let code = match (color, depth) {
+        (Color::TrueColor { .. }, ColorDepth::Basic) =>
+            return paint(text, nearest_basic(color), layer, depth),
+        (Color::TrueColor { r, g, b }, ColorDepth::TrueColor) =>
+            format!("2;{};{};{}", r, g, b),
+        (Color::TrueColor { .. }, ColorDepth::Indexed) =>
+            format!("5;{}", nearest_index(color)),
+        _ => return match layer {
+            Layer::Foreground => text.color(color).to_string(),
+            Layer::Background => text.on_color(color).to_string(),
+        }
+    };
+
+    if !colored::control::SHOULD_COLORIZE.should_colorize() {
+        return text.to_string();
+    }
+
+    // Resets within the text would also end this color, so restore it
+    let start = match layer {
+        Layer::Foreground => format!("\u{1b}[38;{}m", code),
+        Layer::Background => format!("\u{1b}[48;{}m", code),
+    };
+    format!(
+        "{}{}\u{1b}[0m",
+        start,
+        text.replace("\u{1b}[0m", &format!("\u{1b}[0m{}", start))
+    )
+}
+
+/// Returns the squared distance between two colors.
+fn distance(
+    left: Color,
+    right: Color
+) -> u32 {
+    let (r1, g1, b1) = rgb(left);
+    let (r2, g2, b2) = rgb(right);
+    [(r1, r2), (g1, g2), (b1, b2)].iter()
+        .map(|(a, b)| u32::from(a.max(b) - a.min(b)).pow(2))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette_indexes_map_to_colors() {
+        assert_eq!(from_index(9), Color::BrightRed);
+        assert_eq!(
+            from_index(208),
+            Color::TrueColor { r: 0xff, g: 0x87, b: 0x00 });
+        assert_eq!(
+            from_index(244),
+            Color::TrueColor { r: 0x80, g: 0x80, b: 0x80 });
+        assert_eq!(nearest_index(from_index(208)), 208);
+        assert_eq!(
+            nearest_basic(Color::TrueColor { r: 0xf0, g: 0x10, b: 0x08 }),
+            Color::BrightRed);
+    }
+
+    #[test]
+    fn paint_degrades_to_color_depth() {
+        let orange = Color::TrueColor { r: 0xff, g: 0x88, b: 0x00 };
+        let painted = |layer, depth| paint("x", orange, layer, depth);
+
+        if colored::control::SHOULD_COLORIZE.should_colorize() {
+            assert_eq!(
+                painted(Layer::Foreground, ColorDepth::TrueColor),
+                "\u{1b}[38;2;255;136;0mx\u{1b}[0m");
+            assert_eq!(
+                painted(Layer::Background, ColorDepth::Indexed),
+                "\u{1b}[48;5;208mx\u{1b}[0m");
+            assert_eq!(
+                painted(Layer::Foreground, ColorDepth::Basic),
+                "x".color(Color::Yellow).to_string());
+        } else {
+            assert_eq!(painted(Layer::Foreground, ColorDepth::TrueColor), "x");
+        }
+    }
+}
@@ -1,224 +1,1126 @@
-use colored::Color;
-
-#[derive(Debug, Clone)]
-pub enum CellWidth {
-    // The cell width is fixed
-    Fixed(usize),
-    // The cell is always at least a minimum width
-    Minimum(usize),
-    // The cell takes on the width of its content
-    Content,
-}
-
-impl CellWidth {
-    pub fn default() -> CellWidth {
-        CellWidth::Content
-    }
-}
-
-/// Describes how content should be aligned.
-#[derive(Debug, Clone)]
-#[derive(PartialEq)]
-pub enum Alignment {
-    Left,
-    Center,
-    Right
-}
-
-impl Alignment {
-    fn from_token(token: char) -> Option<Alignment> {
-        match token {
-            '<' => Some(Alignment::Left),
-            '^' => Some(Alignment::Center),
-            '>' => Some(Alignment::Right),
-            _ => None
-        }
-    }
-}
-
-/// Describes whether content will wrap or truncate.
-#[derive(Debug, Clone)]
-pub enum Wrap {
-    /// Content will be truncated when over-width
-    Truncate,
-    // Content will wrap when over-width
-    Wrap
-}
-
-impl Wrap {
-    fn from_token(token: char) -> Option<Wrap> {
-        match token {
-            ';' => Some(Wrap::Wrap),
-            '.' => Some(Wrap::Truncate),
-            _ => None
-        }
-    }
-}
-
-#[allow(unused_macros)]
-#[macro_export]
-macro_rules! content_style {
-    ( $style:literal ) => {
-        ContentStyle::from_format($style)
-    }
-}
-
-/// Represents the style to apply to a line of content.
-#[derive(Debug, Clone)]
-pub struct ContentStyle {
-    pub foreground_color: Option<Color>,
-    pub background_color: Option<Color>,
-    pub alignment: Alignment,
-    pub wrap: Wrap,
-    pub width: CellWidth
-}
-
-impl ContentStyle {
-    #[must_use]
-    pub fn default() -> ContentStyle {
-        ContentStyle {
-            foreground_color: None,
-            background_color: None,
-            alignment: Alignment::Left,
-            wrap: Wrap::Truncate,
-            width: CellWidth::Content,
-        }
-    }
-
-    #[must_use]
-    pub fn new(
-        foreground_color: Option<Color>,
-        background_color: Option<Color>,
-        alignment: Alignment,
-        wrap: Wrap,
-        width: CellWidth
-    ) -> ContentStyle {
-        ContentStyle {
-            foreground_color,
-            background_color,
-            alignment,
-            wrap,
-            width,
-        }
-    }
-
-    /// Returns a `ContentStyle` from a format string.
-    ///
-    /// # Arguments
-    ///
-    /// * `format` - The format string to parse.
-    ///
-    /// # Panics
-    ///
-    /// If width specifiers are not well formatted.
-    #[must_use]
-    pub fn from_format(format: &str) -> ContentStyle {
-        // Start with defaults
-        let mut style = ContentStyle::default();
-
-        // Iterate tokens
-        let tokens: Vec<char> = format[1..format.len() - 1].chars().collect();
-        let mut token_ix = 0;
-        while token_ix < tokens.len() {
-            let token = tokens[token_ix];
-
-            // Foreground color
-            if let Some(color) = ContentStyle::color_from_token(token) {
-                style.foreground_color = Some(color)
-            }
-            // Alignment
-            if let Some(alignment) = Alignment::from_token(token) {
-                style.alignment = alignment
-            }
-            // Wrap
-            if let Some(wrap) = Wrap::from_token(token) {
-                style.wrap = wrap
-            }
-
-            // Background color (consumes two tokens)
-            if token == '-' {
-                // Avoid problem if - is last token (with no color code)
-                if tokens.len() > token_ix + 1 {
-                    style.background_color = 
-                        ContentStyle::color_from_token(tokens[token_ix + 1]);
-                    // Consume next token (to skip the background color code)
-                    token_ix += 1;
-                }
-            }
-            token_ix += 1;
-
-            // Width specifier (consumes until matching token)
-            if token == ':' {
-                // TODO: Clean up this logic (should be a common width fn)
-                if let Some(ix) = format[token_ix+1..=tokens.len()].find(':') {
-                    let width = format[token_ix+1..=token_ix+ix].parse::<usize>().unwrap();
-                    style.width = CellWidth::Fixed(width);
-                    token_ix += ix + 1;
-                }
-            }
-
-            // Width specifier (consumes until matching token)
-            if token == '|' {
-                // TODO: Clean up this logic (should be a common width fn)
-
-                if let Some(ix) = format[token_ix+1..=tokens.len()].find('|') {
-                        let width = format[token_ix+1..=token_ix+ix].parse::<usize>().unwrap();
-                        style.width = CellWidth::Minimum(width);
-                        token_ix += ix + 1;
-                }
-            }
-        }
-
-        style
-    }
-
-    fn color_from_token(
-        token: char
-    ) -> Option<Color> {
-        match token {
-            'w' => Some(Color::White),
-            'l' => Some(Color::Black),
-            'r' => Some(Color::Red),
-            'g' => Some(Color::Green),
-            'y' => Some(Color::Yellow),
-            'b' => Some(Color::Blue),
-            'm' => Some(Color::Magenta),
-            'c' => Some(Color::Cyan),
-            'W' => Some(Color::BrightWhite),
-            'L' => Some(Color::BrightBlack),
-            'R' => Some(Color::BrightRed),
-            'G' => Some(Color::BrightGreen),
-            'Y' => Some(Color::BrightYellow),
-            'B' => Some(Color::BrightBlue),
-            'M' => Some(Color::BrightMagenta),
-            'C' => Some(Color::BrightCyan),
-            _ => None,
-        }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use colored::Color;
-
-   #[test]
-   fn from_format_fixed_width() {
-        let style = ContentStyle::from_format("{c^;:15:}");
-   
-        let expected = 
-            ContentStyle {
-                foreground_color: Some(Color::Cyan),
-                background_color: None,
-                alignment: Alignment::Center,
-                wrap: Wrap::Wrap,
-                width: CellWidth::Fixed(15)
-            };
-
-        assert_eq!(
-            format!("{:?}", style),
-            format!("{:?}", expected)
-        );
-    }
-
-}
+use colored::Color;
+use super::color;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellWidth {
+    // The cell width is fixed
+    Fixed(usize),
+    // The cell is always at least a minimum width
+    Minimum(usize),
+    // The cell takes on the width of its content
+    Content,
+}
+
+impl CellWidth {
+    pub fn default() -> CellWidth {
+        CellWidth::Content
+    }
+}
+
+/// Describes how content should be aligned.
+#[derive(Debug, Clone)]
+#[derive(PartialEq)]
+pub enum Alignment {
+    Left,
+    Center,
+    Right
+}
+
+impl Alignment {
+    const fn from_token(token: char) -> Option<Alignment> {
+        match token {
+            '<' => Some(Alignment::Left),
+            '^' => Some(Alignment::Center),
+            '>' => Some(Alignment::Right),
+            _ => None
+        }
+    }
+
+    fn token(&self) -> char {
+        match self {
+            Alignment::Left => '<',
+            Alignment::Center => '^',
+            Alignment::Right => '>',
+        }
+    }
+}
+
+/// Describes whether content will wrap or truncate.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::enum_variant_names)]
+pub enum Wrap {
+    /// Content will be truncated when over-width
+    Truncate,
+    // Content will wrap when over-width
+    Wrap,
+    /// Content will wrap at word boundaries when over-width
+    Word
+}
+
+impl Wrap {
+    const fn from_token(token: char) -> Option<Wrap> {
+        match token {
+            ';' => Some(Wrap::Wrap),
+            '~' => Some(Wrap::Word),
+            '.' => Some(Wrap::Truncate),
+            _ => None
+        }
+    }
+
+    fn token(&self) -> char {
+        match self {
+            Wrap::Truncate => '.',
+            Wrap::Wrap => ';',
+            Wrap::Word => '~',
+        }
+    }
+}
+
+/// Describes the text attributes (such as bold or underline) of content.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextAttributes {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub dimmed: bool,
+    pub strikethrough: bool,
+    pub reversed: bool,
+}
+
+impl TextAttributes {
+    /// Returns text attributes with no attribute set.
+    #[must_use]
+    pub const fn none() -> TextAttributes {
+        TextAttributes {
+            bold: false,
+            italic: false,
+            underline: false,
+            dimmed: false,
+            strikethrough: false,
+            reversed: false,
+        }
+    }
+
+    /// Returns a flag indicating whether no attribute is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The text attributes.
+    #[must_use]
+    pub fn is_empty(
+        self: &TextAttributes
+    ) -> bool {
+        *self == TextAttributes::none()
+    }
+
+    /// Returns these attributes with the attribute for a token set, or
+    ///  `None` if the token is not an attribute token.
+    const fn with_token(
+        self,
+        token: char
+    ) -> Option<TextAttributes> {
+        let mut attributes = self;
+        match token {
+            '*' => attributes.bold = true,
+            '/' => attributes.italic = true,
+            '_' => attributes.underline = true,
+            '%' => attributes.dimmed = true,
+            '=' => attributes.strikethrough = true,
+            '!' => attributes.reversed = true,
+            _ => return None
+        }
+        Some(attributes)
+    }
+
+    /// Returns the directive tokens of the attributes that are set.
+    fn tokens(
+        self: &TextAttributes
+    ) -> String {
+        let flags = [
+            (self.bold, '*'),
+            (self.italic, '/'),
+            (self.underline, '_'),
+            (self.dimmed, '%'),
+            (self.strikethrough, '='),
+            (self.reversed, '!'),
+        ];
+        flags.iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, token)| token)
+            .collect()
+    }
+}
+
+/// The reason a style directive could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StyleParseErrorKind {
+    /// The directive is not enclosed in braces.
+    MissingBraces,
+    /// A character that is not part of the directive grammar.
+    UnknownToken(char),
+    /// A color specifier without its closing bracket.
+    UnterminatedColors,
+    /// A `#` color that is neither six hex digits nor a palette index from
+    ///  0 to 255.
+    InvalidColor,
+    /// A width specifier without its closing delimiter.
+    UnterminatedWidth,
+    /// A width specifier that is not a number.
+    NonNumericWidth,
+}
+
+/// An error returned when parsing a malformed style directive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleParseError {
+    /// The byte offset within the directive at which the error was found.
+    pub position: usize,
+    /// The reason the directive could not be parsed.
+    pub kind: StyleParseErrorKind,
+}
+
+impl StyleParseError {
+    const fn new(
+        position: usize,
+        kind: StyleParseErrorKind
+    ) -> StyleParseError {
+        StyleParseError { position, kind }
+    }
+}
+
+impl std::fmt::Display for StyleParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            StyleParseErrorKind::MissingBraces =>
+                write!(f, "style directive must be enclosed in braces"),
+            StyleParseErrorKind::UnknownToken(token) =>
+                write!(f, "unknown token '{}'", token),
+            StyleParseErrorKind::UnterminatedColors =>
+                write!(f, "unterminated color specifier"),
+            StyleParseErrorKind::InvalidColor =>
+                write!(f, "invalid hex or palette color"),
+            StyleParseErrorKind::UnterminatedWidth =>
+                write!(f, "unterminated width specifier"),
+            StyleParseErrorKind::NonNumericWidth =>
+                write!(f, "width specifier is not a number"),
+        }?;
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl std::error::Error for StyleParseError {}
+
+/// The message for a style parse error, written without allocating so that
+///  it can be given to a panic at compile time.
+struct ErrorMessage {
+    bytes: [u8; 96],
+    length: usize,
+}
+
+impl ErrorMessage {
+    /// Returns the message for a style parse error, matching its `Display`
+    ///  output.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The parse error.
+    const fn new(
+        error: &StyleParseError
+    ) -> ErrorMessage {
+        let mut message = ErrorMessage { bytes: [0; 96], length: 0 };
+        match error.kind {
+            StyleParseErrorKind::MissingBraces =>
+                message.push_str("style directive must be enclosed in braces"),
+            StyleParseErrorKind::UnknownToken(token) => {
+                message.push_str("unknown token '");
+                message.push_char(token);
+                message.push_str("'");
+            },
+            StyleParseErrorKind::UnterminatedColors =>
+                message.push_str("unterminated color specifier"),
+            StyleParseErrorKind::InvalidColor =>
+                message.push_str("invalid hex or palette color"),
+            StyleParseErrorKind::UnterminatedWidth =>
+                message.push_str("unterminated width specifier"),
+            StyleParseErrorKind::NonNumericWidth =>
+                message.push_str("width specifier is not a number"),
+        }
+        message.push_str(" at position ");
+        message.push_number(error.position);
+        message
+    }
+
+    /// Appends text to the message.
+    const fn push_str(
+        self: &mut ErrorMessage,
+        text: &str
+    ) {
+        let bytes = text.as_bytes();
+        let mut ix = 0;
+        while ix < bytes.len() {
+            self.bytes[self.length] = bytes[ix];
+            self.length += 1;
+            ix += 1;
+        }
+    }
+
+    /// Appends a character to the message.
+    const fn push_char(
+        self: &mut ErrorMessage,
+        token: char
+    ) {
+        let mut buffer = [0; 4];
+        self.push_str(token.encode_utf8(&mut buffer));
+    }
+
+    /// Appends a number to the message in decimal.
+    const fn push_number(
+        self: &mut ErrorMessage,
+        number: usize
+    ) {
+        // Digits are found from the least significant, so write them backwards
+        let mut digits = [0; 20];
+        let mut count = 0;
+        let mut rest = number;
+        loop {
+            digits[digits.len() - 1 - count] = b'0' + (rest % 10) as u8;
+            count += 1;
+            rest /= 10;
+            if rest == 0 {
+                break;
+            }
+        }
+        let (_, digits) = digits.split_at(digits.len() - count);
+        match std::str::from_utf8(digits) {
+            Ok(digits) => self.push_str(digits),
+            Err(_) => panic!("digits are ASCII")
+        }
+    }
+
+    /// Returns the text of the message.
+    const fn as_str(
+        self: &ErrorMessage
+    ) -> &str {
+        match std::str::from_utf8(self.bytes.split_at(self.length).0) {
+            Ok(text) => text,
+            Err(_) => panic!("message is built from whole characters")
+        }
+    }
+}
+
+/// Parses a style directive occupying a range of bytes.
+///
+/// The parser is a `const fn` so that the directives given to the macros can
+///  be checked at compile time. Error positions are offsets into `bytes`.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes containing the directive.
+/// * `start` - The index of the opening brace of the directive.
+/// * `stop` - The index just past the closing brace of the directive.
+const fn parse_directive(
+    bytes: &[u8],
+    start: usize,
+    stop: usize
+) -> Result<ContentStyle, StyleParseError> {
+    // Directives are enclosed in braces
+    if start >= stop || bytes[start] != b'{' {
+        return Err(StyleParseError::new(
+            start, StyleParseErrorKind::MissingBraces));
+    }
+    if stop - start < 2 || bytes[stop - 1] != b'}' {
+        return Err(StyleParseError::new(
+            stop, StyleParseErrorKind::MissingBraces));
+    }
+
+    // Start with defaults
+    let mut style = ContentStyle {
+        foreground_color: None,
+        background_color: None,
+        attributes: TextAttributes::none(),
+        alignment: Alignment::Left,
+        wrap: Wrap::Truncate,
+        width: CellWidth::Content,
+    };
+
+    // Iterate tokens (between the braces)
+    let end = stop - 1;
+    let mut token_ix = start + 1;
+    while token_ix < end {
+        let token = bytes[token_ix] as char;
+
+        if token == '#' || ContentStyle::color_from_token(token).is_some() {
+            // Foreground color
+            match parse_color(bytes, token_ix, end) {
+                Ok((color, last_ix)) => {
+                    style.foreground_color = Some(color);
+                    token_ix = last_ix;
+                },
+                Err(error) => return Err(error)
+            }
+        } else if let Some(alignment) = Alignment::from_token(token) {
+            style.alignment = alignment;
+        } else if let Some(wrap) = Wrap::from_token(token) {
+            style.wrap = wrap;
+        } else if let Some(attributes) = style.attributes.with_token(token) {
+            style.attributes = attributes;
+        } else if token == '-' {
+            // Background color (consumes the color that follows)
+            match parse_color(bytes, token_ix + 1, end) {
+                Ok((color, last_ix)) => {
+                    style.background_color = Some(color);
+                    token_ix = last_ix;
+                },
+                Err(error) => return Err(error)
+            }
+        } else if token == '[' {
+            // Color pair (consumes until the closing bracket)
+            match parse_colors(bytes, token_ix, end) {
+                Ok((foreground, background, close_ix)) => {
+                    if foreground.is_some() {
+                        style.foreground_color = foreground;
+                    }
+                    if background.is_some() {
+                        style.background_color = background;
+                    }
+                    token_ix = close_ix;
+                },
+                Err(error) => return Err(error)
+            }
+        } else if token == ':' || token == '|' {
+            // Width specifier (consumes until matching token)
+            match parse_width(bytes, token_ix, end) {
+                Ok((width, close_ix)) => {
+                    style.width =
+                        if token == ':' { CellWidth::Fixed(width) }
+                        else { CellWidth::Minimum(width) };
+                    token_ix = close_ix;
+                },
+                Err(error) => return Err(error)
+            }
+        } else if token == '@' {
+            style.width = CellWidth::Content;
+        } else {
+            return Err(StyleParseError::new(
+                token_ix,
+                StyleParseErrorKind::UnknownToken(char_at(bytes, token_ix))));
+        }
+
+        token_ix += 1;
+    }
+
+    Ok(style)
+}
+
+/// Parses the color at an index of a directive, returning the color and the
+///  index of its last byte.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes containing the directive.
+/// * `ix` - The index of the color.
+/// * `end` - The index of the closing brace of the directive.
+const fn parse_color(
+    bytes: &[u8],
+    ix: usize,
+    end: usize
+) -> Result<(Color, usize), StyleParseError> {
+    match optional_color(bytes, ix, end) {
+        Ok(Some(color)) => Ok(color),
+        Ok(None) => {
+            let token = if ix < end { char_at(bytes, ix) } else { '}' };
+            Err(StyleParseError::new(
+                ix, StyleParseErrorKind::UnknownToken(token)))
+        },
+        Err(error) => Err(error)
+    }
+}
+
+/// Parses the color at an index of a directive, if there is one, returning
+///  the color and the index of its last byte.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes containing the directive.
+/// * `ix` - The index of the color.
+/// * `end` - The index of the closing brace of the directive.
+const fn optional_color(
+    bytes: &[u8],
+    ix: usize,
+    end: usize
+) -> Result<Option<(Color, usize)>, StyleParseError> {
+    if ix >= end {
+        return Ok(None);
+    }
+    if bytes[ix] == b'#' {
+        return match parse_extended_color(bytes, ix, end) {
+            Ok(color) => Ok(Some(color)),
+            Err(error) => Err(error)
+        };
+    }
+    match ContentStyle::color_from_token(bytes[ix] as char) {
+        Some(color) => Ok(Some((color, ix))),
+        None => Ok(None)
+    }
+}
+
+/// Parses a `#` color (six hex digits, or a 256-color palette index),
+///  returning the color and the index of its last byte.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes containing the directive.
+/// * `hash_ix` - The index of the `#`.
+/// * `end` - The index of the closing brace of the directive.
+const fn parse_extended_color(
+    bytes: &[u8],
+    hash_ix: usize,
+    end: usize
+) -> Result<(Color, usize), StyleParseError> {
+    let first_ix = hash_ix + 1;
+
+    // Six hex digits give an RGB color
+    let mut hex_count = 0;
+    while hex_count < 6
+        && first_ix + hex_count < end
+        && bytes[first_ix + hex_count].is_ascii_hexdigit()
+    {
+        hex_count += 1;
+    }
+    if hex_count == 6 {
+        let color = Color::TrueColor {
+            r: hex_byte(bytes, first_ix),
+            g: hex_byte(bytes, first_ix + 2),
+            b: hex_byte(bytes, first_ix + 4),
+        };
+        return Ok((color, first_ix + 5));
+    }
+
+    // Up to three decimal digits give a palette index
+    let mut index: u32 = 0;
+    let mut digit_count = 0;
+    while digit_count < 3
+        && first_ix + digit_count < end
+        && bytes[first_ix + digit_count].is_ascii_digit()
+    {
+        index = index * 10 + (bytes[first_ix + digit_count] - b'0') as u32;
+        digit_count += 1;
+    }
+    if digit_count == 0 || index > 255 {
+        return Err(StyleParseError::new(
+            hash_ix, StyleParseErrorKind::InvalidColor));
+    }
+
+    Ok((color::from_index(index as u8), hash_ix + digit_count))
+}
+
+/// Returns the value of the two hex digits at an index of a directive.
+const fn hex_byte(
+    bytes: &[u8],
+    ix: usize
+) -> u8 {
+    hex_digit(bytes[ix]) * 16 + hex_digit(bytes[ix + 1])
+}
+
+/// Returns the value of a hex digit.
+const fn hex_digit(
+    digit: u8
+) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10
+    }
+}
+
+/// Parses a bracketed color pair starting at its opening bracket, returning
+///  the foreground and background colors and the index of the closing
+///  bracket.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes containing the directive.
+/// * `open_ix` - The index of the opening bracket.
+/// * `end` - The index of the closing brace of the directive.
+const fn parse_colors(
+    bytes: &[u8],
+    open_ix: usize,
+    end: usize
+) -> Result<(Option<Color>, Option<Color>, usize), StyleParseError> {
+    let mut foreground = None;
+    let mut background = None;
+    let mut ix = open_ix + 1;
+
+    // Foreground color
+    match optional_color(bytes, ix, end) {
+        Ok(Some((color, last_ix))) => {
+            foreground = Some(color);
+            ix = last_ix + 1;
+        },
+        Ok(None) => {},
+        Err(error) => return Err(error)
+    }
+
+    // Background color (optionally preceded by -)
+    let color =
+        if ix < end && bytes[ix] == b'-' {
+            match parse_color(bytes, ix + 1, end) {
+                Ok(color) => Ok(Some(color)),
+                Err(error) => Err(error)
+            }
+        } else {
+            optional_color(bytes, ix, end)
+        };
+    match color {
+        Ok(Some((color, last_ix))) => {
+            background = Some(color);
+            ix = last_ix + 1;
+        },
+        Ok(None) => {},
+        Err(error) => return Err(error)
+    }
+
+    if ix == end {
+        Err(StyleParseError::new(
+            open_ix, StyleParseErrorKind::UnterminatedColors))
+    } else if bytes[ix] == b']' {
+        Ok((foreground, background, ix))
+    } else {
+        Err(StyleParseError::new(
+            ix, StyleParseErrorKind::UnknownToken(char_at(bytes, ix))))
+    }
+}
+
+/// Parses a width specifier starting at its opening delimiter, returning the
+///  width and the index of the closing delimiter.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes containing the directive.
+/// * `open_ix` - The index of the opening delimiter.
+/// * `end` - The index of the closing brace of the directive.
+const fn parse_width(
+    bytes: &[u8],
+    open_ix: usize,
+    end: usize
+) -> Result<(usize, usize), StyleParseError> {
+    let delimiter = bytes[open_ix];
+
+    // Find the closing delimiter
+    let mut close_ix = open_ix + 1;
+    while close_ix < end && bytes[close_ix] != delimiter {
+        close_ix += 1;
+    }
+    if close_ix == end {
+        return Err(StyleParseError::new(
+            open_ix, StyleParseErrorKind::UnterminatedWidth));
+    }
+
+    // The width must be a (non-empty) number
+    if close_ix == open_ix + 1 {
+        return Err(StyleParseError::new(
+            close_ix, StyleParseErrorKind::NonNumericWidth));
+    }
+    let mut width: usize = 0;
+    let mut digit_ix = open_ix + 1;
+    while digit_ix < close_ix {
+        let digit = bytes[digit_ix];
+        let next =
+            if digit.is_ascii_digit() {
+                match width.checked_mul(10) {
+                    Some(width) => width.checked_add((digit - b'0') as usize),
+                    None => None
+                }
+            } else {
+                None
+            };
+        match next {
+            Some(next) => width = next,
+            None => return Err(StyleParseError::new(
+                digit_ix, StyleParseErrorKind::NonNumericWidth))
+        }
+        digit_ix += 1;
+    }
+
+    Ok((width, close_ix))
+}
+
+/// Returns the character starting at a byte offset of a directive.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes containing the directive (valid UTF-8).
+/// * `ix` - The offset of the first byte of the character.
+const fn char_at(
+    bytes: &[u8],
+    ix: usize
+) -> char {
+    // The lead byte gives the length of the encoded character
+    let lead = bytes[ix];
+    let (length, mut code) =
+        if lead < 0x80 { (1, lead as u32) }
+        else if lead >= 0xf0 { (4, (lead & 0x07) as u32) }
+        else if lead >= 0xe0 { (3, (lead & 0x0f) as u32) }
+        else { (2, (lead & 0x1f) as u32) };
+
+    let mut byte_ix = ix + 1;
+    while byte_ix < ix + length && byte_ix < bytes.len() {
+        code = (code << 6) | (bytes[byte_ix] & 0x3f) as u32;
+        byte_ix += 1;
+    }
+
+    match char::from_u32(code) {
+        Some(token) => token,
+        None => char::REPLACEMENT_CHARACTER
+    }
+}
+
+/// Builds a `ContentStyle` from a single style directive.
+///
+/// Style directives given as string literals are checked when the code is
+///  compiled, so a malformed directive is a compile error:
+///
+/// ```
+/// use libtableformat::content_style;
+///
+/// let style = content_style!("{^[rG]:15:;}");
+/// ```
+///
+/// ```compile_fail
+/// use libtableformat::content_style;
+///
+/// let style = content_style!("{^[rG]:15:;} {>}");
+/// ```
+#[allow(unused_macros)]
+#[macro_export]
+macro_rules! content_style {
+    ( $style:literal ) => {
+        {
+            // Reject malformed directives at compile time
+            const _: () = $crate::ContentStyle::assert_valid_directive($style);
+            $crate::ContentStyle::from_format($style)
+        }
+    }
+}
+
+/// Represents the style to apply to a line of content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentStyle {
+    pub foreground_color: Option<Color>,
+    pub background_color: Option<Color>,
+    pub attributes: TextAttributes,
+    pub alignment: Alignment,
+    pub wrap: Wrap,
+    pub width: CellWidth
+}
+
+impl Default for ContentStyle {
+    fn default() -> ContentStyle {
+        ContentStyle {
+            foreground_color: None,
+            background_color: None,
+            attributes: TextAttributes::none(),
+            alignment: Alignment::Left,
+            wrap: Wrap::Truncate,
+            width: CellWidth::Content,
+        }
+    }
+}
+
+impl ContentStyle {
+    #[must_use]
+    pub fn new(
+        foreground_color: Option<Color>,
+        background_color: Option<Color>,
+        alignment: Alignment,
+        wrap: Wrap,
+        width: CellWidth
+    ) -> ContentStyle {
+        ContentStyle {
+            foreground_color,
+            background_color,
+            attributes: TextAttributes::none(),
+            alignment,
+            wrap,
+            width,
+        }
+    }
+
+    /// Returns a `ContentStyle` from a format string.
+    ///
+    /// See `try_from_format` for a version that reports malformed format
+    ///  strings.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The format string to parse.
+    ///
+    /// # Panics
+    ///
+    /// If the format string is not a valid style directive.
+    #[must_use]
+    pub fn from_format(format: &str) -> ContentStyle {
+        match ContentStyle::try_from_format(format) {
+            Ok(style) => style,
+            Err(error) =>
+                panic!("invalid style directive '{}': {}", format, error)
+        }
+    }
+
+    /// Returns a `ContentStyle` from a format string, or an error describing
+    ///  where and why the format string is malformed.
+    ///
+    /// A style directive has the following grammar, where each element is
+    ///  optional and may appear in any order:
+    ///
+    /// ```text
+    /// directive := "{" element* "}"
+    /// element   := alignment | colors | attribute | width | wrap
+    /// alignment := "<" | "^" | ">"
+    /// colors    := "[" color? ("-"? color)? "]" | color | "-" color
+    /// width     := ":" digits ":" | "|" digits "|" | "@"
+    /// attribute := "*" | "/" | "_" | "%" | "=" | "!"
+    /// wrap      := ";" | "~" | "."
+    /// color     := "w" | "l" | "r" | "g" | "y" | "b" | "m" | "c"
+    ///            | "W" | "L" | "R" | "G" | "Y" | "B" | "M" | "C"
+    ///            | "#" hex{6} | "#" digit{1,3}
+    /// ```
+    ///
+    /// The bracketed color pair `[fb]` sets the foreground and background
+    ///  colors, and `[-b]` sets only the background. The bare forms (a color
+    ///  code for the foreground, or `-` and a color code for the background)
+    ///  are also accepted. The attribute tokens set bold (`*`), italic (`/`),
+    ///  underline (`_`), dimmed (`%`), strikethrough (`=`) and reversed (`!`)
+    ///  text.
+    ///
+    /// Colors may also be given as `#rrggbb` hex values, or as `#` and an
+    ///  index (0 to 255) into the 256-color xterm palette, such as
+    ///  `{#ff8800-#202020}` or `{[#208]}`. These are shown according to the
+    ///  color depth of the terminal (see `color_depth`).
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The format string to parse.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the format string is not enclosed in braces,
+    ///  contains an unknown token, or has a malformed color or width
+    ///  specifier.
+    pub const fn try_from_format(
+        format: &str
+    ) -> Result<ContentStyle, StyleParseError> {
+        let bytes = format.as_bytes();
+        parse_directive(bytes, 0, bytes.len())
+    }
+
+    /// Checks a format string of space-separated style directives, as used
+    ///  for the content lines of a cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The format string to check.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error for the first malformed directive. Its position is
+    ///  an offset into the whole format string.
+    pub const fn validate_directives(
+        format: &str
+    ) -> Result<(), StyleParseError> {
+        let bytes = format.as_bytes();
+
+        let mut start = 0;
+        while start <= bytes.len() {
+            // Find the end of this directive
+            let mut stop = start;
+            while stop < bytes.len() && bytes[stop] != b' ' {
+                stop += 1;
+            }
+
+            if let Err(error) = parse_directive(bytes, start, stop) {
+                return Err(error);
+            }
+            start = stop + 1;
+        }
+
+        Ok(())
+    }
+
+    /// Panics if a format string is not a single, valid style directive.
+    ///
+    /// Used by `content_style!` and the `TableRow` derive to reject malformed
+    ///  directives at compile time.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The format string to check.
+    ///
+    /// # Panics
+    ///
+    /// If the directive is malformed, naming the problem and its position.
+    #[doc(hidden)]
+    pub const fn assert_valid_directive(
+        format: &str
+    ) {
+        if let Err(error) = ContentStyle::try_from_format(format) {
+            panic!("{}", ErrorMessage::new(&error).as_str());
+        }
+    }
+
+    /// Panics if a format string of space-separated style directives is
+    ///  malformed.
+    ///
+    /// Used by the `cell!`, `row!` and `table!` macros to reject malformed
+    ///  directives at compile time.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The format string to check.
+    ///
+    /// # Panics
+    ///
+    /// If any of the directives is malformed, naming the problem and its
+    ///  position.
+    #[doc(hidden)]
+    pub const fn assert_valid_directives(
+        format: &str
+    ) {
+        if let Err(error) = ContentStyle::validate_directives(format) {
+            panic!("{}", ErrorMessage::new(&error).as_str());
+        }
+    }
+
+    const fn color_from_token(
+        token: char
+    ) -> Option<Color> {
+        match token {
+            'w' => Some(Color::White),
+            'l' => Some(Color::Black),
+            'r' => Some(Color::Red),
+            'g' => Some(Color::Green),
+            'y' => Some(Color::Yellow),
+            'b' => Some(Color::Blue),
+            'm' => Some(Color::Magenta),
+            'c' => Some(Color::Cyan),
+            'W' => Some(Color::BrightWhite),
+            'L' => Some(Color::BrightBlack),
+            'R' => Some(Color::BrightRed),
+            'G' => Some(Color::BrightGreen),
+            'Y' => Some(Color::BrightYellow),
+            'B' => Some(Color::BrightBlue),
+            'M' => Some(Color::BrightMagenta),
+            'C' => Some(Color::BrightCyan),
+            _ => None,
+        }
+    }
+
+    fn color_token(
+        color: Color
+    ) -> String {
+        let token = match color {
+            Color::White => 'w',
+            Color::Black => 'l',
+            Color::Red => 'r',
+            Color::Green => 'g',
+            Color::Yellow => 'y',
+            Color::Blue => 'b',
+            Color::Magenta => 'm',
+            Color::Cyan => 'c',
+            Color::BrightWhite => 'W',
+            Color::BrightBlack => 'L',
+            Color::BrightRed => 'R',
+            Color::BrightGreen => 'G',
+            Color::BrightYellow => 'Y',
+            Color::BrightBlue => 'B',
+            Color::BrightMagenta => 'M',
+            Color::BrightCyan => 'C',
+            Color::TrueColor { r, g, b } =>
+                return format!("#{:02x}{:02x}{:02x}", r, g, b),
+        };
+        token.to_string()
+    }
+}
+
+impl std::fmt::Display for ContentStyle {
+    /// Writes the style as a canonical style directive.
+    ///
+    /// The canonical form is `{alignment[colors]attributes width wrap}`
+    ///  (without the space), leaving out any part that has its default
+    ///  value. RGB colors are written as `#rrggbb`, so parsing the directive
+    ///  returns the same style.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{")?;
+        if self.alignment != Alignment::Left {
+            write!(f, "{}", self.alignment.token())?;
+        }
+
+        let foreground = self.foreground_color.map(ContentStyle::color_token);
+        let background = self.background_color.map(ContentStyle::color_token);
+        match (foreground, background) {
+            // Hex colors are separated for readability
+            (Some(fg), Some(bg)) if fg.len() > 1 || bg.len() > 1 =>
+                write!(f, "[{}-{}]", fg, bg)?,
+            (Some(fg), Some(bg)) => write!(f, "[{}{}]", fg, bg)?,
+            (Some(fg), None) => write!(f, "[{}]", fg)?,
+            (None, Some(bg)) => write!(f, "[-{}]", bg)?,
+            (None, None) => {}
+        }
+        write!(f, "{}", self.attributes.tokens())?;
+
+        match self.width {
+            CellWidth::Fixed(width) => write!(f, ":{}:", width)?,
+            CellWidth::Minimum(width) => write!(f, "|{}|", width)?,
+            CellWidth::Content => {}
+        }
+        if self.wrap != Wrap::Truncate {
+            write!(f, "{}", self.wrap.token())?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use colored::Color;
+
+    #[test]
+    fn try_from_format_reports_position_and_reason() {
+        let error = |format| ContentStyle::try_from_format(format).unwrap_err();
+
+        assert_eq!(
+            error(""),
+            StyleParseError::new(0, StyleParseErrorKind::MissingBraces));
+        assert_eq!(
+            error("{c^"),
+            StyleParseError::new(3, StyleParseErrorKind::MissingBraces));
+        assert_eq!(
+            error("{c^x}"),
+            StyleParseError::new(3, StyleParseErrorKind::UnknownToken('x')));
+        assert_eq!(
+            error("{c-é}"),
+            StyleParseError::new(3, StyleParseErrorKind::UnknownToken('é')));
+        assert_eq!(
+            error("{^:15}"),
+            StyleParseError::new(2, StyleParseErrorKind::UnterminatedWidth));
+        assert_eq!(
+            error("{|1x|}"),
+            StyleParseError::new(3, StyleParseErrorKind::NonNumericWidth));
+        assert_eq!(
+            error("{^:15}").to_string(),
+            "unterminated width specifier at position 2");
+    }
+
+    #[test]
+    fn documented_and_legacy_forms_are_equivalent() {
+        let pairs = [
+            ("{^[rG]:15:;}", "{^r-G:15:;}"),
+            ("{[-g]}", "{-g}"),
+            ("{>[c-B]|10|}", "{>c-B|10|}"),
+            ("{[Y]@}", "{Y.}"),
+        ];
+
+        for (documented, legacy) in pairs.iter() {
+            assert_eq!(
+                ContentStyle::from_format(documented),
+                ContentStyle::from_format(legacy)
+            );
+        }
+        assert_eq!(
+            ContentStyle::try_from_format("{[rx]}").unwrap_err(),
+            StyleParseError::new(3, StyleParseErrorKind::UnknownToken('x')));
+        assert_eq!(
+            ContentStyle::try_from_format("{[r}").unwrap_err(),
+            StyleParseError::new(1, StyleParseErrorKind::UnterminatedColors));
+    }
+
+    #[test]
+    fn display_round_trips_canonical_directive() {
+        let formats = ["{}", "{^[rG]:15:;}", "{>[-b]|3|~}", "{[C]*_!}", "{/%=}"];
+        for format in formats.iter() {
+            let style = ContentStyle::from_format(format);
+
+            assert_eq!(style.to_string(), *format);
+            assert_eq!(ContentStyle::from_format(&style.to_string()), style);
+        }
+        assert_eq!(ContentStyle::from_format("{c-R<.@}").to_string(), "{[cR]}");
+    }
+
+    #[test]
+    fn hex_and_palette_colors() {
+        let orange = Color::TrueColor { r: 0xff, g: 0x88, b: 0x00 };
+        let style = ContentStyle::from_format("{#ff8800-#202020}");
+
+        assert_eq!(style.foreground_color, Some(orange));
+        assert_eq!(
+            style.background_color,
+            Some(Color::TrueColor { r: 0x20, g: 0x20, b: 0x20 }));
+        assert_eq!(style.to_string(), "{[#ff8800-#202020]}");
+        assert_eq!(
+            ContentStyle::from_format("{[#FF8800]>}").foreground_color,
+            Some(orange));
+        assert_eq!(
+            ContentStyle::from_format("{[#208-#1]}").to_string(),
+            "{[#ff8700-r]}");
+        assert_eq!(
+            ContentStyle::try_from_format("{#256}").unwrap_err(),
+            StyleParseError::new(1, StyleParseErrorKind::InvalidColor));
+        assert_eq!(
+            ContentStyle::try_from_format("{-#ff}").unwrap_err(),
+            StyleParseError::new(2, StyleParseErrorKind::InvalidColor));
+    }
+
+    #[test]
+    fn directives_are_checked_in_constants() {
+        const STYLE: Result<ContentStyle, StyleParseError> =
+            ContentStyle::try_from_format("{^[rG]:15:;}");
+        const _: () = ContentStyle::assert_valid_directives("{<} {>[-b]|3|}");
+
+        assert_eq!(STYLE, Ok(ContentStyle::from_format("{^r-G:15:;}")));
+        assert_eq!(
+            ContentStyle::validate_directives("{r} {>:x:}"),
+            Err(StyleParseError::new(
+                7, StyleParseErrorKind::NonNumericWidth)));
+        assert_eq!(
+            ContentStyle::validate_directives("{r}  {>}"),
+            Err(StyleParseError::new(4, StyleParseErrorKind::MissingBraces)));
+    }
+
+    #[test]
+    fn compile_time_messages_match_display() {
+        for format in [
+            "{<", "{x}", "{é}", "{[r}", "{#256}", "{|3}", "{:123x:}"
+        ].iter() {
+            let error = ContentStyle::try_from_format(format).unwrap_err();
+            assert_eq!(ErrorMessage::new(&error).as_str(), error.to_string());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown token '}' at position 2")]
+    fn single_directive_assertion_rejects_directive_lists() {
+        ContentStyle::assert_valid_directives("{<} {>}");
+        ContentStyle::assert_valid_directive("{<} {>}");
+    }
+
+   #[test]
+   fn from_format_fixed_width() {
+        let style = ContentStyle::from_format("{c^;:15:}");
+   
+        let expected = 
+            ContentStyle {
+                foreground_color: Some(Color::Cyan),
+                background_color: None,
+                attributes: TextAttributes::none(),
+                alignment: Alignment::Center,
+                wrap: Wrap::Wrap,
+                width: CellWidth::Fixed(15)
+            };
+
+        assert_eq!(
+            format!("{:?}", style),
+            format!("{:?}", expected)
+        );
+    }
+
+}
@@ -0,0 +1,467 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+const ESC: char = '\u{1b}';
+const SGR_RESET: &str = "\u{1b}[0m";
+
+/// A piece of text that is either visible or an ANSI escape sequence.
+#[derive(Debug, PartialEq)]
+enum Segment<'a> {
+    /// Visible text (may contain several grapheme clusters).
+    Text(&'a str),
+    /// A complete ANSI escape sequence (occupies no columns).
+    Escape(&'a str),
+}
+
+/// Splits a string into visible text and ANSI escape sequence segments.
+///
+/// Recognizes CSI sequences (including SGR color sequences), OSC sequences
+///  terminated by BEL or ST (such as hyperlinks), and two-character escapes.
+///  An unterminated sequence extends to the end of the string.
+fn segments(text: &str) -> Vec<Segment<'_>> {
+    let mut result = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        match rest.find(ESC) {
+            Some(0) => {
+                let len = escape_len(rest);
+                result.push(Segment::Escape(&rest[..len]));
+                rest = &rest[len..];
+            },
+            Some(ix) => {
+                result.push(Segment::Text(&rest[..ix]));
+                rest = &rest[ix..];
+            },
+            None => {
+                result.push(Segment::Text(rest));
+                rest = "";
+            }
+        }
+    }
+    result
+}
+
+/// Returns the byte length of the escape sequence at the start of `text`.
+fn escape_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    match bytes.get(1) {
+        // Control Sequence Introducer (ends with a byte in 0x40..=0x7E)
+        Some(b'[') => {
+            bytes[2..].iter()
+                .position(|b| (0x40..=0x7e).contains(b))
+                .map_or(bytes.len(), |ix| ix + 3)
+        },
+        // Operating System Command (ends with BEL or ESC \)
+        Some(b']') => {
+            let mut ix = 2;
+            while ix < bytes.len() {
+                if bytes[ix] == 0x07 {
+                    return ix + 1;
+                }
+                if bytes[ix] == 0x1b && bytes.get(ix + 1) == Some(&b'\\') {
+                    return ix + 2;
+                }
+                ix += 1;
+            }
+            bytes.len()
+        },
+        // Two character escape
+        Some(_) => 1 + text[1..].chars().next().map_or(0, char::len_utf8),
+        None => 1
+    }
+}
+
+/// Tracks the SGR (color and style) sequences active at a point in text.
+#[derive(Debug, Default)]
+struct ActiveStyle {
+    sequences: Vec<String>,
+}
+
+impl ActiveStyle {
+    /// Updates the active style with an escape sequence.
+    fn apply(&mut self, escape: &str) {
+        // Only SGR sequences (ESC [ ... m) change the style
+        if !escape.starts_with("\u{1b}[") || !escape.ends_with('m') {
+            return;
+        }
+        let params = &escape[2..escape.len() - 1];
+        if params.is_empty() || params == "0" {
+            self.sequences.clear();
+        } else if let Some(remaining) = params.strip_prefix("0;") {
+            self.sequences.clear();
+            self.sequences.push(format!("\u{1b}[{}m", remaining));
+        } else {
+            self.sequences.push(escape.to_string());
+        }
+    }
+
+    /// Returns the sequences needed to re-open the active style.
+    fn open(&self) -> String {
+        self.sequences.concat()
+    }
+
+    fn is_active(&self) -> bool {
+        !self.sequences.is_empty()
+    }
+}
+
+/// Measures the display width of a string, in terminal columns.
+///
+/// Width is measured per grapheme cluster so that combining marks and
+///  zero-width joiners add no width, and double-width East Asian characters
+///  and emoji count as two columns. ANSI escape sequences add no width.
+///
+/// # Arguments
+///
+/// * `text` - The text to measure.
+pub fn display_width(text: &str) -> usize {
+    segments(text).iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => text.graphemes(true)
+                .map(UnicodeWidthStr::width)
+                .sum(),
+            Segment::Escape(_) => 0
+        })
+        .sum()
+}
+
+/// Removes all ANSI escape sequences from a string.
+///
+/// # Arguments
+///
+/// * `text` - The text to strip.
+pub fn strip_escapes(text: &str) -> String {
+    segments(text).iter()
+        .filter_map(|segment| match segment {
+            Segment::Text(text) => Some(*text),
+            Segment::Escape(_) => None
+        })
+        .collect()
+}
+
+/// Splits a string at the last grapheme boundary that fits within a width.
+///
+/// The first part of the result is never wider than `width`. A grapheme that
+///  would straddle the width is moved entirely into the second part. Escape
+///  sequences are never split and those directly following the last fitting
+///  grapheme stay with the first part.
+///
+/// # Arguments
+///
+/// * `text` - The text to split.
+/// * `width` - The maximum display width of the first part.
+pub fn split_at_width(
+    text: &str,
+    width: usize
+) -> (&str, &str) {
+    let mut used = 0;
+    let mut offset = 0;
+    for segment in segments(text) {
+        match segment {
+            Segment::Escape(escape) => offset += escape.len(),
+            Segment::Text(run) => {
+                for (ix, grapheme) in run.grapheme_indices(true) {
+                    let grapheme_width = grapheme.width();
+                    if used + grapheme_width > width {
+                        return text.split_at(offset + ix);
+                    }
+                    used += grapheme_width;
+                }
+                offset += run.len();
+            }
+        }
+    }
+    (text, "")
+}
+
+/// Splits a string into consecutive lines no wider than `width`.
+///
+/// A grapheme wider than `width` is placed on a line of its own so that
+///  splitting always makes progress. Any style opened by an escape sequence
+///  is reset at the end of each line and re-opened at the start of the next.
+///
+/// # Arguments
+///
+/// * `text` - The text to split.
+/// * `width` - The maximum display width of each line.
+pub fn split_into_width(
+    text: &str,
+    width: usize
+) -> Vec<String> {
+    // Text with no visible content is still a single, empty line
+    if display_width(text) == 0 {
+        return vec!(String::new());
+    }
+
+    let mut lines = Vec::new();
+    let mut style = ActiveStyle::default();
+    let mut remaining = text;
+    while display_width(remaining) > 0 {
+        let (mut part, mut rest) = split_at_width(remaining, width);
+        if display_width(part) == 0 {
+            // Grapheme is wider than the column (take it anyway)
+            let first_len = first_grapheme_end(remaining);
+            part = &remaining[..first_len];
+            rest = &remaining[first_len..];
+        }
+        lines.push(close_line(&mut style, part));
+        remaining = rest;
+    }
+    // Trailing escape sequences (such as a final reset) are dropped
+    lines
+}
+
+/// Splits a string into lines no wider than `width`, breaking at words.
+///
+/// Lines break after whitespace or after a hyphen that follows a word.
+///  Words longer than `width` are broken at the width. Leading whitespace on
+///  continuation lines and trailing whitespace on every line are trimmed.
+///  Any style opened by an escape sequence is reset at the end of each line
+///  and re-opened at the start of the next.
+///
+/// # Arguments
+///
+/// * `text` - The text to split.
+/// * `width` - The maximum display width of each line.
+pub fn wrap_words(
+    text: &str,
+    width: usize
+) -> Vec<String> {
+    // Text with no visible content is still a single, empty line
+    if display_width(text) == 0 {
+        return vec!(String::new());
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut style = ActiveStyle::default();
+
+    // The atoms on the line being built and their visible width
+    let mut line: Vec<Segment> = Vec::new();
+    let mut used = 0;
+    // The position on the line after which it may be broken
+    let mut last_break: Option<usize> = None;
+    let mut after_word = false;
+
+    for atom in atoms(text) {
+        let grapheme = match atom {
+            Segment::Escape(_) => {
+                line.push(atom);
+                continue;
+            },
+            Segment::Text(grapheme) => grapheme
+        };
+        let grapheme_width = grapheme.width();
+        let is_space = grapheme.chars().all(char::is_whitespace);
+
+        // Trim leading whitespace on continuation lines
+        if is_space && used == 0 && !lines.is_empty() {
+            continue;
+        }
+
+        if used > 0 && used + grapheme_width > width {
+            if is_space {
+                // Break at the whitespace itself
+                flush_line(&mut lines, &mut style, &mut line);
+                used = 0;
+                last_break = None;
+                after_word = false;
+                continue;
+            }
+            // Move the partial word to the next line (or hard break)
+            let carry = match last_break {
+                Some(ix) => line.split_off(ix),
+                None => Vec::new()
+            };
+            flush_line(&mut lines, &mut style, &mut line);
+            line = carry;
+            used = atoms_width(&line);
+            last_break = None;
+            // The partial word is itself too long so hard break it
+            if used > 0 && used + grapheme_width > width {
+                flush_line(&mut lines, &mut style, &mut line);
+                used = 0;
+            }
+        }
+
+        line.push(atom);
+        used += grapheme_width;
+        if is_space || (grapheme == "-" && after_word) {
+            last_break = Some(line.len());
+        }
+        after_word = !is_space;
+    }
+
+    if atoms_width(&line) > 0 {
+        flush_line(&mut lines, &mut style, &mut line);
+    }
+
+    lines
+}
+
+/// Splits a string into escape sequences and single grapheme clusters.
+fn atoms(text: &str) -> Vec<Segment<'_>> {
+    let mut result = Vec::new();
+    for segment in segments(text) {
+        match segment {
+            Segment::Escape(_) => result.push(segment),
+            Segment::Text(run) => {
+                result.extend(run.graphemes(true).map(Segment::Text));
+            }
+        }
+    }
+    result
+}
+
+/// Measures the visible width of a list of atoms.
+fn atoms_width(atoms: &[Segment]) -> usize {
+    atoms.iter()
+        .map(|atom| match atom {
+            Segment::Text(grapheme) => grapheme.width(),
+            Segment::Escape(_) => 0
+        })
+        .sum()
+}
+
+/// Completes a line of atoms (trimming trailing whitespace) and appends it
+///  to `lines`.
+fn flush_line(
+    lines: &mut Vec<String>,
+    style: &mut ActiveStyle,
+    line: &mut Vec<Segment>
+) {
+    let mut end = line.len();
+    while end > 0 {
+        match line[end - 1] {
+            Segment::Text(grapheme)
+                if grapheme.chars().all(char::is_whitespace) => {
+                line.remove(end - 1);
+            },
+            Segment::Text(_) => break,
+            Segment::Escape(_) => {}
+        }
+        end -= 1;
+    }
+
+    let text = line.drain(..)
+        .map(|atom| match atom {
+            Segment::Text(text) | Segment::Escape(text) => text
+        })
+        .collect::<String>();
+    lines.push(close_line(style, &text));
+}
+
+/// Truncates a string to fit within a width, closing any open style.
+///
+/// # Arguments
+///
+/// * `text` - The text to truncate.
+/// * `width` - The maximum display width of the result.
+pub fn truncate_to_width(
+    text: &str,
+    width: usize
+) -> String {
+    let (truncated, rest) = split_at_width(text, width);
+    if rest.is_empty() {
+        truncated.to_string()
+    } else {
+        close_line(&mut ActiveStyle::default(), truncated)
+    }
+}
+
+/// Returns a line prefixed with the style that was active before it and
+///  suffixed with a reset if a style is still active at its end.
+fn close_line(
+    style: &mut ActiveStyle,
+    line: &str
+) -> String {
+    let mut result = style.open();
+    result.push_str(line);
+    for segment in segments(line) {
+        if let Segment::Escape(escape) = segment {
+            style.apply(escape);
+        }
+    }
+    if style.is_active() {
+        result.push_str(SGR_RESET);
+    }
+    result
+}
+
+/// Returns the byte offset just past the first visible grapheme (including
+///  any escape sequences preceding it).
+fn first_grapheme_end(text: &str) -> usize {
+    let mut offset = 0;
+    for segment in segments(text) {
+        match segment {
+            Segment::Escape(escape) => offset += escape.len(),
+            Segment::Text(run) => {
+                return offset + run.graphemes(true).next().map_or(0, str::len);
+            }
+        }
+    }
+    offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_width_counts_columns() {
+        assert_eq!(display_width("héllo"), 5);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("👨\u{200d}👩\u{200d}👧"), 2);
+    }
+
+    #[test]
+    fn display_width_ignores_escape_sequences() {
+        assert_eq!(display_width("\u{1b}[31mred\u{1b}[0m"), 3);
+        assert_eq!(
+            display_width("\u{1b}]8;;http://x\u{1b}\\link\u{1b}]8;;\u{1b}\\"),
+            4
+        );
+    }
+
+    #[test]
+    fn split_at_width_keeps_wide_graphemes_whole() {
+        assert_eq!(split_at_width("日本語", 3), ("日", "本語"));
+        assert_eq!(split_at_width("ab\u{301}c", 2), ("ab\u{301}", "c"));
+        assert_eq!(
+            split_into_width("日本語", 1),
+            vec!("日", "本", "語")
+        );
+    }
+
+    #[test]
+    fn split_into_width_reopens_active_style() {
+        assert_eq!(
+            split_into_width("\u{1b}[31mabcd\u{1b}[0mef", 2),
+            vec!(
+                "\u{1b}[31mab\u{1b}[0m",
+                "\u{1b}[31mcd\u{1b}[0m",
+                "ef"
+            )
+        );
+        assert_eq!(
+            truncate_to_width("\u{1b}[1m\u{1b}[32mbold green", 4),
+            "\u{1b}[1m\u{1b}[32mbold\u{1b}[0m"
+        );
+    }
+
+    #[test]
+    fn wrap_words_breaks_at_word_boundaries() {
+        assert_eq!(
+            wrap_words("the quick  brown fox", 10),
+            vec!("the quick", "brown fox")
+        );
+        assert_eq!(
+            wrap_words("well-known words", 8),
+            vec!("well-", "known", "words")
+        );
+        assert_eq!(
+            wrap_words("a verylongword", 5),
+            vec!("a", "veryl", "ongwo", "rd")
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use super::content::Content;
 
+/// A data item provides the contents of a single table cell.
 pub struct DataItem {
     pub lines: Vec<Content>
 }
@@ -13,6 +14,27 @@ impl std::str::FromStr for DataItem {
 }
 
 impl DataItem {
+    /// Returns a data item with a content line for each line of some text.
+    ///
+    /// Empty text still produces a single, empty line.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text of the data item.
+    #[must_use]
+    pub fn from_text(
+        text: &str
+    ) -> DataItem {
+        let mut lines: Vec<Content> = text.lines()
+            .map(|line| Content::new(line.to_string(), None))
+            .collect();
+        if lines.is_empty() {
+            lines.push(Content::new(String::new(), None));
+        }
+        DataItem::from(lines)
+    }
+
+    #[must_use]
     pub fn from(
         lines: Vec<Content>
     ) -> DataItem {
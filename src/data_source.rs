@@ -1,91 +1,39 @@
+use super::data_item::DataItem;
+use super::table::row::Row;
+
+/// A source of rows of data for a table.
+///
+/// Each row provides a data item for each of its cells. Any iterable of rows
+///  (such as a `Vec<Vec<DataItem>>`) is a data source.
 pub trait DataSource {
+    /// The iterator over the rows of the source.
+    type Rows: Iterator<Item=Vec<DataItem>>;
+
+    /// Returns the number of columns of the source, if known.
+    ///
+    /// Tables built from the source have at least this many columns.
+    fn column_count(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the column headers of the source, if it has any.
+    ///
+    /// These are used when a table is built without column headers.
+    fn column_headers(&self) -> Option<Row> {
+        None
+    }
+
+    /// Returns the rows of the source.
+    fn into_rows(self) -> Self::Rows;
 }
 
+impl<I> DataSource for I
+    where
+        I: IntoIterator<Item=Vec<DataItem>>
+{
+    type Rows = I::IntoIter;
 
-// use super::data_item::DataItem;
-
-// /// Pretend data source for testing multi-line scenarios.
-// pub struct WSDataFrame {
-//     current_byte_ix: usize,
-//     num_bytes: usize,
-// }
-
-// impl WSDataFrame {
-//     pub fn from(base64: String) -> WSDataFrame {
-//         // Pretend to calc number of bytes
-//         let num_bytes = 6;
-
-//         // Data will be simulated in iterator
-
-//         WSDataFrame {
-//             current_byte_ix: 0,
-//             num_bytes,
-//         }
-//     }
-
-//     fn format_byte_one(self: &WSDataFrame) -> DataItem {
-//         DataItem::from(vec![
-//             "1|0|0|0|0 0 0 1",
-//             "F|R|R|R| text  ",
-//             "I|S|S|S|op code",
-//             "N|V|V|V| (4 b) ",
-//             " |1|2|3|       ",
-//         ])
-        
-//     }
-
-//     fn format_byte_two(self: &WSDataFrame) -> DataItem {
-//         DataItem::from(vec![
-//             "0|0 0 0 0 1 0 0",
-//             "M|   4 bytes   ",
-//             "A|(Payload len)",
-//             "S|  (7 bits)   ",
-//             "K|             ",
-//         ])
-//     }
-
-//     fn format_payload_byte(self: &WSDataFrame, byte_index: usize) -> DataItem {
-//         if byte_index < 5 {
-
-        
-//         DataItem::from(vec![
-//             "1 0 0 1 0 0 0 1",
-//             "               ",
-//             &format!(" (Payload pt {})", byte_index + 1),
-//             "    (8 bits)   ",
-//         ])
-//         }
-//         else {
-//             DataItem::from(vec![
-//                 "1 0 0 1 0 0 0 1",
-//                 "               ",
-//                 &format!(" (Payload part the {})", byte_index + 1),
-//                 "    (8 bits)   ",
-//                 "  ++",
-//             ])  
-//         }
-//     }
-// }
-
-// impl Iterator for WSDataFrame {
-//     type Item = DataItem;
-
-//     fn next(&mut self) -> Option<DataItem> {
-//         if self.current_byte_ix < self.num_bytes {
-//             // Get the next byte of data
-//             let item = match self.current_byte_ix {
-//                 0 => self.format_byte_one(),
-//                 1 => self.format_byte_two(),
-//                 _ => self.format_payload_byte(self.current_byte_ix),
-//             };
-
-//             // Advance the byte index
-//             self.current_byte_ix += 1;
-
-//             Some(item)
-//         } else {
-//             // No more data items to process
-//             None
-//         }
-//     }
-// }
+    fn into_rows(self) -> Self::Rows {
+        self.into_iter()
+    }
+}
@@ -2,19 +2,34 @@ mod content;
 mod content_iterator;
 mod data_item;
 mod data_source;
-mod table_data_source;
+mod terminal;
 pub mod table;
 
-pub use content::ContentStyle;
+// Lets generated code refer to this crate by name from within it
+#[cfg(all(test, feature = "derive"))]
+extern crate self as libtableformat;
+
+pub use content::{
+    ContentStyle, StyleParseError, StyleParseErrorKind, TextAttributes
+};
+pub use data_item::DataItem;
+pub use data_source::DataSource;
+pub use table::row::TableRow;
+#[cfg(feature = "derive")]
+pub use table_format_derive::TableRow;
+pub use terminal::{
+    color_depth, set_color_depth, terminal_width, ColorDepth,
+    DEFAULT_TERMINAL_WIDTH
+};
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::env;
     use table::{Border, Table};
     use table::row::Row;
     use table::cell::Cell;
     use colored::Color;
+    use content::text::strip_escapes;
 
     #[test]
     fn test_simple_table() {
@@ -24,7 +39,7 @@ mod tests {
                 "Fish", "15", "Pizza", "10", "Steak", "6"
             );
 
-        let output = table.format();
+        let output = strip_escapes(&table.format());
         println!("{}", output);
 
         let expected = "+----------+----------+\n|   Food   |  Count   |\n+----------+----------+\n|Fish      |15        |\n+----------+----------+\n|Pizza     |10        |\n+----------+----------+\n|Steak     |6         |\n+----------+----------+\n";
@@ -43,9 +58,10 @@ mod tests {
         println!("{}", output);
 
         let expected =
-            match env::var("NO_COLOR") {
-                Ok(_) => "+---------------+----------+\n|     Food      |  Count   |\n+---------------+----------+\n|Fish           |3         |\n+---------------+----------+\n|Pears          |5         |\n+---------------+----------+\n|Pizza          |13        |\n+---------------+----------+\n",
-                Err(_) => "+---------------+----------+\n|\u{1b}[36m     Food      \u{1b}[0m|\u{1b}[31m  Count   \u{1b}[0m|\n+---------------+----------+\n|Fish           |3         |\n+---------------+----------+\n|Pears          |5         |\n+---------------+----------+\n|Pizza          |13        |\n+---------------+----------+\n",
+            if colored::control::SHOULD_COLORIZE.should_colorize() {
+                "\u{1b}[36m+---------------+----------+\u{1b}[0m\n\u{1b}[36m|\u{1b}[0m\u{1b}[36m     Food      \u{1b}[0m\u{1b}[36m|\u{1b}[0m\u{1b}[31m  Count   \u{1b}[0m\u{1b}[36m|\u{1b}[0m\n\u{1b}[36m+---------------+----------+\u{1b}[0m\n\u{1b}[36m|\u{1b}[0mFish           \u{1b}[36m|\u{1b}[0m3         \u{1b}[36m|\u{1b}[0m\n\u{1b}[36m+---------------+----------+\u{1b}[0m\n\u{1b}[36m|\u{1b}[0mPears          \u{1b}[36m|\u{1b}[0m5         \u{1b}[36m|\u{1b}[0m\n\u{1b}[36m+---------------+----------+\u{1b}[0m\n\u{1b}[36m|\u{1b}[0mPizza          \u{1b}[36m|\u{1b}[0m13        \u{1b}[36m|\u{1b}[0m\n\u{1b}[36m+---------------+----------+\u{1b}[0m\n"
+            } else {
+                "+---------------+----------+\n|     Food      |  Count   |\n+---------------+----------+\n|Fish           |3         |\n+---------------+----------+\n|Pears          |5         |\n+---------------+----------+\n|Pizza          |13        |\n+---------------+----------+\n"
             };
 
         assert_eq!(output, expected);
@@ -79,16 +95,18 @@ mod tests {
             horizontal_split_intersect_top: '┴',
             horizontal_split_intersect_bottom: '┬',
             horizontal_split_intersect_both: '┼',
-            color: Color::Red
+            color: Color::Red,
+            ..Border::default()
         };
 
         let output = table.format();
         println!("{}", output);
 
         let expected =
-            match env::var("NO_COLOR") {
-                Ok(_) => "┌──────────┬────────────────────┐\n│  Custom  │      Borders       │\n├──────────┼────────────────────┤\n│are       │super fun           │\n├──────────┼────────────────────┤\n│and       │super awesome       │\n└──────────┴────────────────────┘\n",
-                Err(_) => "┌──────────┬────────────────────┐\n│\u{1b}[91m  Custom  \u{1b}[0m│\u{1b}[96m      Borders       \u{1b}[0m│\n├──────────┼────────────────────┤\n│are       │super fun           │\n├──────────┼────────────────────┤\n│and       │super awesome       │\n└──────────┴────────────────────┘\n",
+            if colored::control::SHOULD_COLORIZE.should_colorize() {
+                "\u{1b}[31m┌──────────┬────────────────────┐\u{1b}[0m\n\u{1b}[31m│\u{1b}[0m\u{1b}[91m  Custom  \u{1b}[0m\u{1b}[31m│\u{1b}[0m\u{1b}[96m      Borders       \u{1b}[0m\u{1b}[31m│\u{1b}[0m\n\u{1b}[31m├──────────┼────────────────────┤\u{1b}[0m\n\u{1b}[31m│\u{1b}[0mare       \u{1b}[31m│\u{1b}[0msuper fun           \u{1b}[31m│\u{1b}[0m\n\u{1b}[31m├──────────┼────────────────────┤\u{1b}[0m\n\u{1b}[31m│\u{1b}[0mand       \u{1b}[31m│\u{1b}[0msuper awesome       \u{1b}[31m│\u{1b}[0m\n\u{1b}[31m└──────────┴────────────────────┘\u{1b}[0m\n"
+            } else {
+                "┌──────────┬────────────────────┐\n│  Custom  │      Borders       │\n├──────────┼────────────────────┤\n│are       │super fun           │\n├──────────┼────────────────────┤\n│and       │super awesome       │\n└──────────┴────────────────────┘\n"
             };
 
         assert_eq!(output, expected);
@@ -1,20 +1,65 @@
 mod border;
+mod delimited;
+mod grid;
+mod html;
+#[cfg(feature = "json")]
+mod json;
+mod layout;
+mod markdown;
 pub mod row;
 pub mod cell;
 
 use std::str::FromStr;
-pub use border::Border;
+pub use border::{
+    Border, BorderLines, ParseBorderError, RowSeparators, BORDER_PRESETS
+};
+pub use delimited::{CsvOptions, DelimitedOptions};
+pub use html::HtmlStyle;
+#[cfg(feature = "json")]
+pub use json::{JsonOptions, JsonTableError, NestedValues};
 use super::data_item::DataItem;
+use super::data_source::DataSource;
 use cell::Cell;
+use grid::Grid;
 use row::Row;
 use crate::content::{ContentStyle, CellWidth};
 
+/// Builds a table from header cells (`style => header`), optional base
+///  cell styles, and the data for the body.
+///
+/// Style directives given as string literals are checked when the code is
+///  compiled, so a malformed directive is a compile error:
+///
+/// ```
+/// use libtableformat::table;
+/// use libtableformat::table::Table;
+/// use libtableformat::table::cell::Cell;
+/// use libtableformat::table::row::Row;
+///
+/// let table = table!(
+///     "{^:10:}" => "Food", "{^:10:}" => "Count";
+///     "Fish", "15", "Pizza", "10"
+/// );
+/// ```
+///
+/// ```compile_fail
+/// use libtableformat::table;
+/// use libtableformat::table::Table;
+/// use libtableformat::table::cell::Cell;
+/// use libtableformat::table::row::Row;
+///
+/// let table = table!(
+///     "{^:10:}" => "Food", "{^:10:}" => "Count";
+///     "{[r}";
+///     "Fish", "15", "Pizza", "10"
+/// );
+/// ```
 #[allow(unused_macros)]
 #[macro_export]
 macro_rules! table {
     // Simple format
     ( $($style:literal=>$header:literal),*; $($data:literal),* ) => {
-        table!(
+        $crate::table!(
             $($style => $header),*;
             "{}";
             $($data),*
@@ -28,9 +73,9 @@ macro_rules! table {
     {
         Table::from_vec(
             // Header specification
-            crate::row!($($style => $header), *),
+            $crate::row!($($style => $header), *),
             // Base cell styles
-            &[$(crate::content_style!($cell_style)),*],
+            &[$($crate::content_style!($cell_style)),*],
             // Data
             &[$($data),*]
         )
@@ -40,6 +85,15 @@ macro_rules! table {
 #[derive(Debug)]
 pub struct Table {
     pub border: Border,
+    /// The border lines to draw (such as the outer frame and row separators).
+    pub border_lines: BorderLines,
+    /// The cell rendered above the row headers, left of the column headers.
+    pub row_header_corner: Cell,
+    /// The maximum width of the formatted table, in chars (if limited).
+    pub max_width: Option<usize>,
+    /// An optional row of group headers above the column headers, whose
+    ///  cells typically span several columns.
+    pub column_groups: Option<Row>,
     column_breaks: Vec<CellWidth>,
     column_headers: Row,
     row_headers: Vec<Cell>,
@@ -52,6 +106,10 @@ impl Table {
     pub fn empty() -> Table {
         Table {
             border: Border::default(),
+            row_header_corner: Cell::empty(),
+            border_lines: BorderLines::default(),
+            max_width: None,
+            column_groups: None,
             column_breaks: Vec::new(),
             column_headers: Row::new(),
             row_headers: Vec::new(),
@@ -78,6 +136,10 @@ impl Table {
     ) -> Table {
         Table {
             border,
+            row_header_corner: Cell::empty(),
+            border_lines: BorderLines::default(),
+            max_width: None,
+            column_groups: None,
             column_breaks,
             column_headers,
             row_headers,
@@ -102,71 +164,98 @@ impl Table {
         cell_styles: &[ContentStyle],
         data: &[&str]
     ) -> Table {
-        // Build data items from string vector source
-        let d: Vec<DataItem> = 
-            data.iter().map(|i| DataItem::from_str(i).unwrap())
-                .collect::<Vec<DataItem>>();
+        // Split the data into rows as wide as the column headers
+        let column_count = column_headers.column_count().max(1);
+        let rows: Vec<Vec<DataItem>> = data.chunks(column_count)
+            .map(|row| row.iter()
+                .map(|i| DataItem::from_str(i).unwrap())
+                .collect())
+            .collect();
 
         Table::from_data_source(
             column_headers,
-            &cell_styles,
+            cell_styles,
             Vec::new(),
-            d.iter()
+            rows
+        )
+    }
+
+    /// Returns a table with a row for each value.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The values to show.
+    #[must_use]
+    pub fn from_table_rows<T: row::TableRow>(
+        values: &[T]
+    ) -> Table {
+        Table::from_data_source(
+            T::header_row(),
+            &T::cell_styles(),
+            Vec::new(),
+            values.iter().map(row::TableRow::data_items)
         )
     }
 
     /// Returns a table built from a data source.
     ///
+    /// Each row of the source becomes a row of the table. If no column
+    ///  headers are given, the column headers of the source (if any) are
+    ///  used.
+    ///
     /// # Arguments
     ///
-    /// * `column_headers` - The header row describes how to split the data.
-    /// * `cell_styles` - The base styles to apply to each cell.
+    /// * `column_headers` - The header row describes the columns.
+    /// * `cell_styles` - The base styles to apply to the cells of each column.
     /// * `row_headers` - The row headers to put before each row.
-    /// * `data_source` - An iterable source providing the table body data.
-    pub fn from_data_source<'a, I>(
+    /// * `data_source` - The source providing the rows of the table body.
+    pub fn from_data_source<S>(
         column_headers: Row,
         cell_styles: &[ContentStyle],
         row_headers: Vec<Cell>,
-        data_source: I,
-    ) -> Table 
-        where 
-            I: Iterator<Item=&'a DataItem>
+        data_source: S,
+    ) -> Table
+        where
+            S: DataSource
     {
-        let mut data_rows = Vec::new();
+        let column_headers =
+            if column_headers.is_empty() {
+                data_source.column_headers().unwrap_or_default()
+            } else {
+                column_headers
+            };
 
-        // Derive column breaks from column headers
+        // Derive column breaks from column headers (spanning headers are
+        //  sized by content across each of their columns)
         let mut column_breaks: Vec<CellWidth> = Vec::new();
         for cell in column_headers.iter() {
-            column_breaks.push(cell.get_cell_width());
-        }
-
-        // Create a new row
-        let mut row_ix = 0;
-        data_rows.push(Row::new());
-
-        let mut break_ix = 0;
-
-        for item in data_source {
-            // Add a new row if needed
-            if break_ix == column_breaks.len() {
-                break_ix = 0;
-                data_rows.push(Row::new());
-                row_ix += 1;
+            if cell.column_span() == 1 {
+                column_breaks.push(cell.get_cell_width());
+            } else {
+                column_breaks.extend(
+                    (0..cell.column_span()).map(|_| CellWidth::Content));
             }
+        }
 
-            // Get the cell style
-            let mut cell_style = &ContentStyle::default();
-            if cell_styles.len() > break_ix {
-                cell_style = &cell_styles[break_ix];
+        // Columns reported by the source but without a header fit content
+        if let Some(column_count) = data_source.column_count() {
+            if column_count > column_breaks.len() {
+                column_breaks.resize(column_count, CellWidth::Content);
             }
-
-            data_rows[row_ix].add_cell(
-                Cell::from_data_item(item, cell_style.clone())
-            );
-
-            break_ix += 1;
         }
 
+        let data_rows: Vec<Row> = data_source.into_rows()
+            .map(|items| Row::from(
+                items.iter()
+                    .enumerate()
+                    .map(|(column_ix, item)| Cell::from_data_item(
+                        item,
+                        cell_styles.get(column_ix).cloned().unwrap_or_default()
+                    ))
+                    .collect()
+            ))
+            .collect();
+
         Table::new(
             Border::default(),
             column_breaks,
@@ -178,133 +267,221 @@ impl Table {
 
     /// Returns the contents of a table formatted as a string.
     ///
+    /// If the table has a `max_width` then it is formatted to fit within
+    ///  that width (see `format_with_width`).
+    ///
     /// # Arguments
     ///
     /// * `self` - The table to format.
     #[must_use]
     pub fn format(self: &Table) -> String {
-        let mut result: String = String::from("");
-
-        // Measure column widths
-        let widths = self.measure_column_widths();
-
-        // Format header row
-        result.push_str(&self.format_header(&widths));
-
-        // Format table body
-        result.push_str(&self.format_body(&widths));
-
-        result
+        match self.max_width {
+            Some(maximum_width) => self.format_with_width(maximum_width),
+            None => {
+                let empty = Cell::empty();
+                let grid = self.build_grid(&empty);
+                let widths = self.measure_column_widths(&grid);
+                self.format_columns(&grid, &widths)
+            }
+        }
     }
 
-    /// Formats the table's column headers.
+    /// Returns the contents of a table formatted to fit within a width.
+    ///
+    /// `Content` and `Minimum` columns are shrunk (widest first) until the
+    ///  table fits. `Fixed` columns and the row header column are never
+    ///  shrunk and `Minimum` columns are never shrunk below their minimum, so
+    ///  the table may still be wider than `maximum_width` if it cannot fit.
+    ///  Cells in shrunk columns wrap or truncate according to their style.
     ///
     /// # Arguments
     ///
-    /// * `self` - The table containing the column headers to format.
-    fn format_header(
+    /// * `self` - The table to format.
+    /// * `maximum_width` - The maximum render width, in chars.
+    #[must_use]
+    pub fn format_with_width(
         self: &Table,
-        widths: &[usize]
+        maximum_width: usize
     ) -> String {
-        let mut result: String = String::from("");
-
-        // Print top border
-        result.push_str(&self.border.format_top(&widths));
-        result.push('\n');
+        let empty = Cell::empty();
+        let grid = self.build_grid(&empty);
+        let mut widths = self.measure_column_widths(&grid);
+
+        // Borders and the row header column take up part of the width
+        let mut reserved = self.border_lines.measure_width(widths.len());
+        if let Some(row_header_width) = self.measure_row_header_width() {
+            reserved += row_header_width + self.border_lines.split_width();
+        }
 
-        // Render column header row
-        result.push_str(
-            &self.column_headers.format(
-                &self.border,
-                &self.column_breaks
-            )
+        layout::shrink_to_fit(
+            &mut widths,
+            &self.column_breaks,
+            maximum_width.saturating_sub(reserved)
         );
 
-        // Print horizontal split beneath headers
-        result.push_str(&self.border.format_horizontal_split(&widths));
-        result.push('\n');
-
-        result
+        self.format_columns(&grid, &widths)
     }
 
-    /// Formats the body of a table.
+    /// Returns the contents of a table formatted to fit the terminal.
     ///
-    /// The specified `width` describes a desired output size and will be the
-    ///  maximum size of the formatted output. However, the table may also be
-    ///  formatted to a shorter width if there are insufficient column widths
-    ///  available to justify the full value.
+    /// The terminal width is detected using `terminal_width`.
     ///
     /// # Arguments
     ///
-    /// * `self` - The table being formatted.
-    /// * `maximum_width` - The maximum render width, in chars.
-    fn format_body(
+    /// * `self` - The table to format.
+    #[must_use]
+    pub fn format_for_terminal(self: &Table) -> String {
+        self.format_with_width(crate::terminal_width())
+    }
+
+    /// Formats the table with resolved column widths.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table to format.
+    /// * `grid` - The grid of table cells.
+    /// * `column_widths` - The render width of each column.
+    fn format_columns(
         self: &Table,
-        widths: &[usize]
+        grid: &Grid,
+        column_widths: &[usize]
     ) -> String {
-        let mut result: String = String::from("");
-
-        // Iterate rows
-        for row_ix in 0..self.data_rows.len() {
-            let row = &self.data_rows[row_ix];
-            result.push_str(
-                &row.format(
-                    &self.border,
-                    &self.column_breaks
-                )
+        // Grid widths include the row header column, if any
+        let mut widths = column_widths.to_vec();
+        if let Some(row_header_width) = self.measure_row_header_width() {
+            widths.insert(0, row_header_width);
+        }
+
+        grid.format(&self.border, &self.border_lines, &widths)
+    }
+
+    /// Places the cells of the table into a grid.
+    ///
+    /// The header section contains the column groups (if any) and the
+    ///  column headers (if any). The body section contains the data rows. When the
+    ///  table has row headers they form the first column of the grid, with
+    ///  the corner cell above them. Row headers are used in order for each
+    ///  row not covered by a row header spanning down from an earlier row.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table containing the cells.
+    /// * `empty` - An empty cell used to fill gaps in the row header column.
+    fn build_grid<'a>(
+        self: &'a Table,
+        empty: &'a Cell
+    ) -> Grid<'a> {
+        let has_row_headers = !self.row_headers.is_empty();
+
+        // Header section
+        let mut header_rows: Vec<Vec<&Cell>> = Vec::new();
+        if let Some(groups) = &self.column_groups {
+            header_rows.push(
+                has_row_headers.then_some(empty).into_iter()
+                    .chain(groups.iter())
+                    .collect()
             );
+        }
+        if !self.column_headers.is_empty() {
+            header_rows.push(
+                has_row_headers.then_some(&self.row_header_corner).into_iter()
+                    .chain(self.column_headers.iter())
+                    .collect()
+            );
+        }
 
-            // Print horizontal split beneath all but last row
-            if row_ix < self.data_rows.len() - 1 {
-                result.push_str(
-                    &self.border.format_horizontal_split(&widths));
-                result.push('\n');
+        // Body section
+        let mut row_headers = self.row_headers.iter();
+        let mut header_covered_until = 0;
+        let mut body_rows: Vec<Vec<&Cell>> = Vec::new();
+        for (row_ix, row) in self.data_rows.iter().enumerate() {
+            let mut cells: Vec<&Cell> = Vec::new();
+            if has_row_headers && row_ix >= header_covered_until {
+                let row_header = row_headers.next().unwrap_or(empty);
+                header_covered_until = row_ix + row_header.row_span();
+                cells.push(row_header);
             }
+            cells.extend(row.iter());
+            body_rows.push(cells);
         }
 
-        // Print bottom border at end of table
-        result.push_str(&self.border.format_bottom(&widths));
-        result.push('\n');
+        let column_count = usize::from(has_row_headers) + self.column_count();
+        Grid::new(column_count, vec!(header_rows, body_rows))
+    }
 
-        result
+    /// Returns the number of columns in the table (excluding the row header
+    ///  column).
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table being measured.
+    fn column_count(
+        self: &Table
+    ) -> usize {
+        self.column_groups.iter()
+            .chain(std::iter::once(&self.column_headers))
+            .chain(self.data_rows.iter())
+            .map(Row::column_count)
+            .max()
+            .unwrap_or(0)
+            .max(self.column_breaks.len())
     }
 
-    /// Measures the widths of the columns of a table.
+    /// Measures the natural widths of the columns of a table.
     ///
     /// Column breaks are used to constrain the render width of columns and
-    ///  are considered along with the content of the header cells.
+    ///  are considered along with the content of the header and body cells.
     ///
     /// # Arguments
     ///
     /// * `self` - The table being measured.
+    /// * `grid` - The grid of table cells.
     fn measure_column_widths(
-        self: &Table
+        self: &Table,
+        grid: &Grid
     ) -> Vec<usize> {
-        let mut widths = Vec::new();
-
-        // Iterate through the header row
-        let content_break = CellWidth::Content;
-        for (column_break_ix, cell) in self.column_headers.iter().enumerate() {
-            // Get the next column break (if one is available)
-            let column_break: &CellWidth =
-                if column_break_ix < self.column_breaks.len() {
-                    &self.column_breaks[column_break_ix]
-                } else {
-                    // Use content-width break for additional columns
-                    &content_break
-                };
-            // Calculate the width of this header cell
-            widths.push(cell.measure_width(column_break));
+        // Skip the row header column (which is measured separately)
+        let offset = usize::from(!self.row_headers.is_empty());
+        layout::measure_natural_widths(
+            &self.column_breaks,
+            self.column_count(),
+            self.border_lines.split_width(),
+            grid.placements()
+                .filter(|(column_ix, _, _)| *column_ix >= offset)
+                .map(|(column_ix, column_span, cell)|
+                    (column_ix - offset, column_span, cell))
+        )
+    }
+
+    /// Measures the width of the row header column.
+    ///
+    /// The row header column is as wide as its widest cell, including the
+    ///  corner cell. Returns `None` if the table has no row headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table being measured.
+    fn measure_row_header_width(
+        self: &Table
+    ) -> Option<usize> {
+        if self.row_headers.is_empty() {
+            return None;
         }
 
-        widths
+        let width = self.row_headers.iter()
+            .chain(std::iter::once(&self.row_header_corner))
+            .map(|cell| cell.measure_width(&cell.get_cell_width()))
+            .max()
+            .unwrap_or(0);
+
+        Some(width)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::env;
+    use crate::content::text::strip_escapes;
 
     /// Tests the simple format table! macro.
     ///
@@ -320,9 +497,10 @@ mod tests {
         );
 
         let expected =
-            match env::var("NO_COLOR") {
-                Ok(_) => "+------------+-------+\n|    Food    | Count |\n+------------+-------+\n|Fish        |15     |\n+------------+-------+\n|Pizza       |10     |\n+------------+-------+\n|Tomato      |24     |\n+------------+-------+\n",
-                Err(_) => "+------------+-------+\n|\u{1b}[94m    Food    \u{1b}[0m|\u{1b}[92m Count \u{1b}[0m|\n+------------+-------+\n|Fish        |15     |\n+------------+-------+\n|Pizza       |10     |\n+------------+-------+\n|Tomato      |24     |\n+------------+-------+\n",
+            if colored::control::SHOULD_COLORIZE.should_colorize() {
+                "\u{1b}[36m+------------+-------+\u{1b}[0m\n\u{1b}[36m|\u{1b}[0m\u{1b}[94m    Food    \u{1b}[0m\u{1b}[36m|\u{1b}[0m\u{1b}[92m Count \u{1b}[0m\u{1b}[36m|\u{1b}[0m\n\u{1b}[36m+------------+-------+\u{1b}[0m\n\u{1b}[36m|\u{1b}[0mFish        \u{1b}[36m|\u{1b}[0m15     \u{1b}[36m|\u{1b}[0m\n\u{1b}[36m+------------+-------+\u{1b}[0m\n\u{1b}[36m|\u{1b}[0mPizza       \u{1b}[36m|\u{1b}[0m10     \u{1b}[36m|\u{1b}[0m\n\u{1b}[36m+------------+-------+\u{1b}[0m\n\u{1b}[36m|\u{1b}[0mTomato      \u{1b}[36m|\u{1b}[0m24     \u{1b}[36m|\u{1b}[0m\n\u{1b}[36m+------------+-------+\u{1b}[0m\n"
+            } else {
+                "+------------+-------+\n|    Food    | Count |\n+------------+-------+\n|Fish        |15     |\n+------------+-------+\n|Pizza       |10     |\n+------------+-------+\n|Tomato      |24     |\n+------------+-------+\n"
             };
 
         assert_eq!(
@@ -340,9 +518,10 @@ mod tests {
         );
 
         let expected =
-            match env::var("NO_COLOR") {
-                Ok(_) => "+----------+----------+\n|      Item|     Price|\n+----------+----------+\n|  Basic   |$5,000    |\n+----------+----------+\n|  Super   |$12,000   |\n+----------+----------+\n| Ultimate |$35,000   |\n+----------+----------+\n",
-                Err(_) => "+----------+----------+\n|\u{1b}[35m      Item\u{1b}[0m|\u{1b}[35m     Price\u{1b}[0m|\n+----------+----------+\n|\u{1b}[36m  Basic   \u{1b}[0m|\u{1b}[32m$5,000    \u{1b}[0m|\n+----------+----------+\n|\u{1b}[36m  Super   \u{1b}[0m|\u{1b}[32m$12,000   \u{1b}[0m|\n+----------+----------+\n|\u{1b}[36m Ultimate \u{1b}[0m|\u{1b}[32m$35,000   \u{1b}[0m|\n+----------+----------+\n"
+            if colored::control::SHOULD_COLORIZE.should_colorize() {
+                "\u{1b}[36m+----------+----------+\u{1b}[0m\n\u{1b}[36m|\u{1b}[0m\u{1b}[35m      Item\u{1b}[0m\u{1b}[36m|\u{1b}[0m\u{1b}[35m     Price\u{1b}[0m\u{1b}[36m|\u{1b}[0m\n\u{1b}[36m+----------+----------+\u{1b}[0m\n\u{1b}[36m|\u{1b}[0m\u{1b}[36m  Basic   \u{1b}[0m\u{1b}[36m|\u{1b}[0m\u{1b}[32m$5,000    \u{1b}[0m\u{1b}[36m|\u{1b}[0m\n\u{1b}[36m+----------+----------+\u{1b}[0m\n\u{1b}[36m|\u{1b}[0m\u{1b}[36m  Super   \u{1b}[0m\u{1b}[36m|\u{1b}[0m\u{1b}[32m$12,000   \u{1b}[0m\u{1b}[36m|\u{1b}[0m\n\u{1b}[36m+----------+----------+\u{1b}[0m\n\u{1b}[36m|\u{1b}[0m\u{1b}[36m Ultimate \u{1b}[0m\u{1b}[36m|\u{1b}[0m\u{1b}[32m$35,000   \u{1b}[0m\u{1b}[36m|\u{1b}[0m\n\u{1b}[36m+----------+----------+\u{1b}[0m\n"
+            } else {
+                "+----------+----------+\n|      Item|     Price|\n+----------+----------+\n|  Basic   |$5,000    |\n+----------+----------+\n|  Super   |$12,000   |\n+----------+----------+\n| Ultimate |$35,000   |\n+----------+----------+\n"
             };
 
         assert_eq!(
@@ -350,4 +529,309 @@ mod tests {
             expected
         );
     }
+
+    #[test]
+    fn row_headers_render_left_of_body() {
+        let mut table = Table::from_data_source(
+            crate::row!("{^:6:}" => "Fish", "{^:6:}" => "Steak"),
+            &[],
+            vec!(crate::cell!("{>}", "Mon"), crate::cell!("{>}", "Tuesday")),
+            vec!(
+                vec!(DataItem::from_text("3"), DataItem::from_text("1")),
+                vec!(DataItem::from_text("5"), DataItem::from_text("2"))
+            )
+        );
+        table.row_header_corner = crate::cell!("{}", "Day");
+
+        let expected = "+-------+------+------+\n|Day    | Fish |Steak |\n+-------+------+------+\n|    Mon|3     |1     |\n+-------+------+------+\n|Tuesday|5     |2     |\n+-------+------+------+\n";
+
+        assert_eq!(
+            strip_escapes(&table.format()),
+            expected
+        );
+    }
+
+    #[test]
+    fn data_source_provides_headers_and_columns() {
+        struct Inventory {
+            counts: Vec<(&'static str, usize, &'static str)>,
+        }
+
+        impl DataSource for Inventory {
+            type Rows = std::vec::IntoIter<Vec<DataItem>>;
+
+            fn column_count(&self) -> Option<usize> {
+                Some(3)
+            }
+
+            fn column_headers(&self) -> Option<Row> {
+                Some(crate::row!("{}" => "Item", "{}" => "Count"))
+            }
+
+            fn into_rows(self) -> Self::Rows {
+                self.counts.into_iter()
+                    .map(|(item, count, note)| vec!(
+                        DataItem::from_text(item),
+                        DataItem::from_text(&count.to_string()),
+                        DataItem::from_text(note)
+                    ))
+                    .collect::<Vec<Vec<DataItem>>>()
+                    .into_iter()
+            }
+        }
+
+        let table = Table::from_data_source(
+            Row::new(),
+            &[],
+            Vec::new(),
+            Inventory {
+                counts: vec!(("Fish", 3, "fresh"), ("Pears", 12, "ripe"))
+            }
+        );
+
+        assert_eq!(table.column_count(), 3);
+
+        let expected = "+-----+-----+-----+\n|Item |Count|     |\n+-----+-----+-----+\n|Fish |3    |fresh|\n+-----+-----+-----+\n|Pears|12   |ripe |\n+-----+-----+-----+\n";
+
+        assert_eq!(
+            strip_escapes(&table.format()),
+            expected
+        );
+    }
+
+    #[test]
+    fn format_with_width_shrinks_columns() {
+        let table = table!(
+            "{}" => "Name", "{|6|}" => "Notes";
+            "{}", "{~}";
+            "Ann", "likes long walks", "Bob", "ok"
+        );
+
+        let expected = "+----+-------------+\n|Name|Notes        |\n+----+-------------+\n|Ann |likes long   |\n|    |walks        |\n+----+-------------+\n|Bob |ok           |\n+----+-------------+\n";
+
+        assert_eq!(
+            strip_escapes(&table.format_with_width(20)),
+            expected
+        );
+    }
+
+    #[test]
+    fn format_with_width_truncates_narrow_columns() {
+        let table = table!(
+            "{}" => "Alpha", "{}" => "Omega";
+            "Anchovies", "Octopus"
+        );
+
+        assert_eq!(
+            strip_escapes(&table.format_with_width(13)),
+            "+-----+-----+\n|Alpha|Omega|\n+-----+-----+\n|An...|Oc...|\n+-----+-----+\n"
+        );
+        // Columns too narrow for an ellipsis are cut at the column width
+        assert_eq!(
+            strip_escapes(&table.format_with_width(9)),
+            "+---+---+\n|Alp|Ome|\n+---+---+\n|Anc|Oct|\n+---+---+\n"
+        );
+    }
+
+    #[test]
+    fn column_groups_span_columns() {
+        let mut table = table!(
+            "{^}" => "Jan", "{^}" => "Feb", "{^}" => "Mar", "{^}" => "Apr";
+            "1", "2", "3", "4"
+        );
+        let mut q1 = crate::cell!("{^}", "Q1");
+        q1.set_column_span(2);
+        let mut q2 = crate::cell!("{^}", "Q2");
+        q2.set_column_span(2);
+        table.column_groups = Some(Row::from(vec!(q1, q2)));
+
+        let expected = "+-------+-------+\n|  Q1   |  Q2   |\n+---+---+---+---+\n|Jan|Feb|Mar|Apr|\n+---+---+---+---+\n|1  |2  |3  |4  |\n+---+---+---+---+\n";
+
+        assert_eq!(
+            strip_escapes(&table.format()),
+            expected
+        );
+    }
+
+    #[test]
+    fn section_row_spans_all_columns() {
+        let mut section = crate::cell!("{}", "Fruit and vegetables");
+        section.set_column_span(2);
+        let table = Table::new(
+            Border::default(),
+            vec!(CellWidth::Content, CellWidth::Fixed(5)),
+            crate::row!("{}" => "Food", "{:5:}" => "Count"),
+            Vec::new(),
+            vec!(
+                Row::from(vec!(section)),
+                crate::row!("{}", "Apples", "4")
+            )
+        );
+
+        let expected = "+--------------+-----+\n|Food          |Count|\n+--------------+-----+\n|Fruit and vegetables|\n+--------------+-----+\n|Apples        |4    |\n+--------------+-----+\n";
+
+        assert_eq!(
+            strip_escapes(&table.format()),
+            expected
+        );
+    }
+
+    #[test]
+    fn category_cell_spans_rows() {
+        let mut category = crate::cell!("{}", "Fruit");
+        category.set_row_span(2);
+        let table = Table::new(
+            Border::default(),
+            vec!(CellWidth::Content, CellWidth::Content),
+            crate::row!("{}", "Type", "Name"),
+            Vec::new(),
+            vec!(
+                Row::from(vec!(category, crate::cell!("{}", "Apple"))),
+                crate::row!("{}", "Pear"),
+                crate::row!("{}", "Root", "Carrot")
+            )
+        );
+
+        let expected = "+-----+------+\n|Type |Name  |\n+-----+------+\n|Fruit|Apple |\n|     +------+\n|     |Pear  |\n+-----+------+\n|Root |Carrot|\n+-----+------+\n";
+
+        assert_eq!(
+            strip_escapes(&table.format()),
+            expected
+        );
+    }
+
+    #[test]
+    fn box_drawing_junctions_follow_spans() {
+        let border = Border::single();
+        let mut weekend = crate::cell!("{}", "Wknd");
+        weekend.set_row_span(2);
+        let mut closed = crate::cell!("{}", "Closed");
+        closed.set_column_span(2);
+        let mut table = Table::new(
+            border,
+            vec!(CellWidth::Content, CellWidth::Content),
+            crate::row!("{}", "AM", "PM"),
+            vec!(crate::cell!("{}", "Fri"), weekend),
+            vec!(
+                crate::row!("{}", "9", "5"),
+                crate::row!("{}", "10", "4"),
+                Row::from(vec!(closed))
+            )
+        );
+        table.row_header_corner = crate::cell!("{}", "Day");
+
+        let expected = "┌────┬──┬───┐\n│Day │AM│PM │\n├────┼──┼───┤\n│Fri │9 │5  │\n├────┼──┼───┤\n│Wknd│10│4  │\n│    ├──┴───┤\n│    │Closed│\n└────┴──────┘\n";
+
+        assert_eq!(
+            strip_escapes(&table.format()),
+            expected
+        );
+    }
+
+    #[test]
+    fn border_parts_use_their_own_colors() {
+        let mut table = table!(
+            "{}" => "A", "{}" => "B";
+            "1", "2", "3", "4"
+        );
+        table.border.frame_color = Some(colored::Color::Red);
+        table.border.header_separator_color = Some(colored::Color::Yellow);
+        table.border.grid_color = Some(colored::Color::Blue);
+
+        let expected =
+            if colored::control::SHOULD_COLORIZE.should_colorize() {
+                "\u{1b}[31m+-+-+\u{1b}[0m\n\u{1b}[31m|\u{1b}[0mA\u{1b}[34m|\u{1b}[0mB\u{1b}[31m|\u{1b}[0m\n\u{1b}[31m+\u{1b}[0m\u{1b}[33m-+-\u{1b}[0m\u{1b}[31m+\u{1b}[0m\n\u{1b}[31m|\u{1b}[0m1\u{1b}[34m|\u{1b}[0m2\u{1b}[31m|\u{1b}[0m\n\u{1b}[31m+\u{1b}[0m\u{1b}[34m-+-\u{1b}[0m\u{1b}[31m+\u{1b}[0m\n\u{1b}[31m|\u{1b}[0m3\u{1b}[34m|\u{1b}[0m4\u{1b}[31m|\u{1b}[0m\n\u{1b}[31m+-+-+\u{1b}[0m\n"
+            } else {
+                "+-+-+\n|A|B|\n+-+-+\n|1|2|\n+-+-+\n|3|4|\n+-+-+\n"
+            };
+
+        assert_eq!(
+            table.format(),
+            expected
+        );
+    }
+
+    #[test]
+    fn borderless_lines_separate_columns_with_gaps() {
+        let mut table = table!(
+            "{}" => "NAME", "{}" => "STATUS";
+            "web-1", "Running", "db-1", "Pending"
+        );
+        table.border_lines = BorderLines::borderless(3);
+
+        let expected = "NAME    STATUS \nweb-1   Running\ndb-1    Pending\n";
+
+        assert_eq!(
+            strip_escapes(&table.format()),
+            expected
+        );
+    }
+
+    #[test]
+    fn column_groups_without_splits_keep_column_widths() {
+        let mut table = table!(
+            "{|3|}" => "A", "{|3|}" => "B";
+            "1", "2"
+        );
+        let mut group = crate::cell!("{}", "AB");
+        group.set_column_span(2);
+        table.column_groups = Some(Row::from(vec!(group)));
+        table.border_lines = BorderLines::borderless(0);
+
+        assert_eq!(
+            strip_escapes(&table.format()),
+            "AB    \nA  B  \n1  2  \n"
+        );
+    }
+
+    #[test]
+    fn row_separators_every_n_rows_without_vertical_splits() {
+        let mut table = table!(
+            "{}" => "N", "{}" => "Sq";
+            "1", "1", "2", "4", "3", "9"
+        );
+        table.border_lines = BorderLines {
+            vertical_splits: false,
+            row_separators: RowSeparators::EveryN(2),
+            ..BorderLines::default()
+        };
+
+        let expected = "+----+\n|N Sq|\n+----+\n|1 1 |\n|2 4 |\n+----+\n|3 9 |\n+----+\n";
+
+        assert_eq!(
+            strip_escapes(&table.format()),
+            expected
+        );
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derived_table_rows_use_field_options() {
+        fn dollars(cents: &u32) -> String {
+            format!("${}.{:02}", cents / 100, cents % 100)
+        }
+
+        #[derive(crate::TableRow)]
+        struct Item {
+            #[table(header = "Item")]
+            name: &'static str,
+            #[table(style = "{>:8:}", formatter = "dollars")]
+            price: u32,
+            #[table(skip)]
+            #[allow(dead_code)]
+            sku: u64,
+        }
+
+        let table = Table::from_table_rows(&[
+            Item { name: "Fish", price: 500, sku: 1 },
+            Item { name: "Steak", price: 1250, sku: 2 },
+        ]);
+
+        let expected = "+-----+--------+\n|Item |   price|\n+-----+--------+\n|Fish |   $5.00|\n+-----+--------+\n|Steak|  $12.50|\n+-----+--------+\n";
+
+        assert_eq!(
+            strip_escapes(&table.format()),
+            expected
+        );
+    }
 }
@@ -1,5 +1,23 @@
 use colored::Color;
+use crate::content::color::{self, Layer};
+use crate::terminal::color_depth;
 
+/// The parts of a table border, which may each be colored differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderPart {
+    /// The outer frame around the table.
+    Frame,
+    /// The line between the header and the body of the table.
+    HeaderSeparator,
+    /// The lines between rows and columns inside the table.
+    Grid
+}
+
+/// Table borders.
+///
+/// Border glyphs are drawn in `color` unless a color is set for a specific
+///  part of the border. Colors are not applied when colored output is
+///  disabled (such as when `NO_COLOR` is set).
 #[derive(Debug)]
 pub struct Border {
     pub top_left: char,
@@ -22,12 +40,14 @@ pub struct Border {
     pub horizontal_split_intersect_top: char,
     pub horizontal_split_intersect_bottom: char,
     pub horizontal_split_intersect_both: char,
-    pub color: Color
+    pub color: Color,
+    pub frame_color: Option<Color>,
+    pub header_separator_color: Option<Color>,
+    pub grid_color: Option<Color>
 }
 
-impl Border {
-    #[must_use]
-    pub fn default() -> Border {
+impl Default for Border {
+    fn default() -> Border {
         Border {
             top_left: '+',
             top: '-',
@@ -49,96 +69,606 @@ impl Border {
             horizontal_split_intersect_top: '+',
             horizontal_split_intersect_bottom: '+',
             horizontal_split_intersect_both: '+',
-            color: Color::Cyan
+            color: Color::Cyan,
+            frame_color: None,
+            header_separator_color: None,
+            grid_color: None
         }
     }
+}
+
+/// Which of the rows of a table body are followed by a separator line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowSeparators {
+    /// No separators between body rows.
+    None,
+    /// A separator after every body row.
+    Every,
+    /// A separator after every N body rows.
+    EveryN(usize)
+}
 
-    /// Formats the top border
+impl RowSeparators {
+    /// Returns whether a separator is drawn after a body row.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The row separators.
+    /// * `row_ix` - The index of the row within the table body.
     #[must_use]
-    pub fn format_top(
-        self: &Border,
-        widths: &[usize]
-    ) -> String {
-        let mut result: String = String::from(self.top_left);
-        for ix in 0..widths.len() {
-            result.push_str(
-              &(0..widths[ix])
-                  .map(|_| self.top)
-                  .collect::<String>()
-            );
-            if ix < widths.len() - 1 {
-               result.push_str(String::from(self.top_split).as_str());
-            }
+    pub fn draws_after(self: &RowSeparators, row_ix: usize) -> bool {
+        match self {
+            RowSeparators::None => false,
+            RowSeparators::Every => true,
+            RowSeparators::EveryN(count) =>
+                *count != 0 && (row_ix + 1) % *count == 0
+        }
+    }
+}
+
+/// The lines of the border that are drawn for a table.
+///
+/// When vertical splits are not drawn, columns are separated by
+///  `column_gap` spaces instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BorderLines {
+    pub outer_frame: bool,
+    pub vertical_splits: bool,
+    pub header_separator: bool,
+    pub row_separators: RowSeparators,
+    pub column_gap: usize
+}
+
+impl Default for BorderLines {
+    fn default() -> BorderLines {
+        BorderLines {
+            outer_frame: true,
+            vertical_splits: true,
+            header_separator: true,
+            row_separators: RowSeparators::Every,
+            column_gap: 1
         }
-        result.push_str(String::from(self.top_right).as_str());
-        result
     }
+}
 
-    /// Formats the bottom border
+impl BorderLines {
+    /// Returns border lines for a compact, borderless column layout (similar
+    ///  to the output of `ls` or `kubectl`).
+    ///
+    /// # Arguments
+    ///
+    /// * `column_gap` - The number of spaces between columns.
     #[must_use]
-    pub fn format_bottom(
-        self: &Border,
-        widths: &[usize]
-    ) -> String {
-        let mut result: String = String::from(self.bottom_left);
-        for ix in 0..widths.len() {
-            result.push_str(
-                &(0..widths[ix])
-                    .map(|_| self.bottom)
-                    .collect::<String>()
-            );
-            if ix < widths.len() - 1 {
-                result.push_str(String::from(self.bottom_split).as_str());
-            }
+    pub fn borderless(column_gap: usize) -> BorderLines {
+        BorderLines {
+            outer_frame: false,
+            vertical_splits: false,
+            header_separator: false,
+            row_separators: RowSeparators::None,
+            column_gap
         }
-        result.push_str(String::from(self.bottom_right).as_str());
-        result
     }
 
-    /// Formats the left border.
+    /// Returns the width of the split between two columns.
     #[must_use]
-    pub fn format_left(
-        self: &Border
-    ) -> String {
-        format!("{}", self.left)
+    pub fn split_width(self: &BorderLines) -> usize {
+        if self.vertical_splits { 1 } else { self.column_gap }
     }
 
-    /// Formats the right border.
+    /// Returns the width taken by the borders of a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The border lines.
+    /// * `column_count` - The number of columns in the table.
     #[must_use]
-    pub fn format_right(
-        self: &Border
-    ) -> String {
-        format!("{}", self.right)
+    pub fn measure_width(self: &BorderLines, column_count: usize) -> usize {
+        let frame = if self.outer_frame { 2 } else { 0 };
+        frame + column_count.saturating_sub(1) * self.split_width()
+    }
+}
+
+/// Returns a border that uses the same glyph for every junction.
+///
+/// # Arguments
+///
+/// * `horizontal` - The glyph for horizontal lines.
+/// * `vertical` - The glyph for vertical lines.
+/// * `corners` - The glyphs for the top left, top right, bottom left and
+///   bottom right corners.
+/// * `junction` - The glyph for all other points where lines meet.
+fn uniform_border(
+    horizontal: char,
+    vertical: char,
+    (top_left, top_right, bottom_left, bottom_right): (char, char, char, char),
+    junction: char
+) -> Border {
+    Border {
+        top_left,
+        top: horizontal,
+        top_right,
+        top_split: junction,
+        bottom_left,
+        bottom: horizontal,
+        bottom_right,
+        bottom_split: junction,
+        left: vertical,
+        left_split: junction,
+        right: vertical,
+        right_split: junction,
+        vertical_split: vertical,
+        vertical_split_intersect_left: junction,
+        vertical_split_intersect_right: junction,
+        vertical_split_intersect_both: junction,
+        horizontal_split: horizontal,
+        horizontal_split_intersect_top: junction,
+        horizontal_split_intersect_bottom: junction,
+        horizontal_split_intersect_both: junction,
+        ..Border::default()
+    }
+}
+
+/// Returns a border drawn with box-drawing characters.
+///
+/// # Arguments
+///
+/// * `horizontal` - The glyph for horizontal lines.
+/// * `vertical` - The glyph for vertical lines.
+/// * `corners` - The glyphs for the top left, top right, bottom left and
+///   bottom right corners.
+/// * `junctions` - The glyphs for junctions opening down, up, right, left and
+///   in all directions (`┬`, `┴`, `├`, `┤` and `┼` for single lines).
+fn box_border(
+    horizontal: char,
+    vertical: char,
+    (top_left, top_right, bottom_left, bottom_right): (char, char, char, char),
+    (down, up, right, left, cross): (char, char, char, char, char)
+) -> Border {
+    Border {
+        top_left,
+        top: horizontal,
+        top_right,
+        top_split: down,
+        bottom_left,
+        bottom: horizontal,
+        bottom_right,
+        bottom_split: up,
+        left: vertical,
+        left_split: right,
+        right: vertical,
+        right_split: left,
+        vertical_split: vertical,
+        vertical_split_intersect_left: left,
+        vertical_split_intersect_right: right,
+        vertical_split_intersect_both: cross,
+        horizontal_split: horizontal,
+        horizontal_split_intersect_top: up,
+        horizontal_split_intersect_bottom: down,
+        horizontal_split_intersect_both: cross,
+        ..Border::default()
+    }
+}
+
+/// The names of the border presets, as accepted by `Border::from_str`.
+pub const BORDER_PRESETS: [&str; 9] = [
+    "ascii",
+    "single",
+    "double",
+    "heavy",
+    "rounded",
+    "dashed",
+    "ascii-compact",
+    "dots",
+    "blank",
+];
+
+/// An error returned when parsing an unknown border preset name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBorderError {
+    name: String
+}
+
+impl std::fmt::Display for ParseBorderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown border preset '{}' (expected one of: {})",
+            self.name,
+            BORDER_PRESETS.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for ParseBorderError {}
+
+impl std::str::FromStr for Border {
+    type Err = ParseBorderError;
+
+    /// Returns the border preset with the given name.
+    ///
+    /// Names are matched ignoring case, and `_` may be used in place of `-`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "ascii" => Ok(Border::ascii()),
+            "single" => Ok(Border::single()),
+            "double" => Ok(Border::double()),
+            "heavy" => Ok(Border::heavy()),
+            "rounded" => Ok(Border::rounded()),
+            "dashed" => Ok(Border::dashed()),
+            "ascii-compact" => Ok(Border::ascii_compact()),
+            "dots" => Ok(Border::dots()),
+            "blank" => Ok(Border::blank()),
+            _ => Err(ParseBorderError { name: s.to_string() })
+        }
+    }
+}
+
+impl Border {
+    /// Returns an ASCII border (the default).
+    ///
+    /// ```text
+    /// +----+----+
+    /// |Fish|15  |
+    /// +----+----+
+    /// ```
+    #[must_use]
+    pub fn ascii() -> Border {
+        Border::default()
+    }
+
+    /// Returns a single-line box border.
+    ///
+    /// ```text
+    /// ┌────┬────┐
+    /// │Fish│15  │
+    /// └────┴────┘
+    /// ```
+    #[must_use]
+    pub fn single() -> Border {
+        box_border('─', '│', ('┌', '┐', '└', '┘'), ('┬', '┴', '├', '┤', '┼'))
+    }
+
+    /// Returns a double-line box border.
+    ///
+    /// ```text
+    /// ╔════╦════╗
+    /// ║Fish║15  ║
+    /// ╚════╩════╝
+    /// ```
+    #[must_use]
+    pub fn double() -> Border {
+        box_border('═', '║', ('╔', '╗', '╚', '╝'), ('╦', '╩', '╠', '╣', '╬'))
+    }
+
+    /// Returns a heavy-line box border.
+    ///
+    /// ```text
+    /// ┏━━━━┳━━━━┓
+    /// ┃Fish┃15  ┃
+    /// ┗━━━━┻━━━━┛
+    /// ```
+    #[must_use]
+    pub fn heavy() -> Border {
+        box_border('━', '┃', ('┏', '┓', '┗', '┛'), ('┳', '┻', '┣', '┫', '╋'))
+    }
+
+    /// Returns a single-line box border with rounded corners.
+    ///
+    /// ```text
+    /// ╭────┬────╮
+    /// │Fish│15  │
+    /// ╰────┴────╯
+    /// ```
+    #[must_use]
+    pub fn rounded() -> Border {
+        box_border('─', '│', ('╭', '╮', '╰', '╯'), ('┬', '┴', '├', '┤', '┼'))
+    }
+
+    /// Returns a single-line box border drawn with dashed lines.
+    ///
+    /// ```text
+    /// ┌╌╌╌╌┬╌╌╌╌┐
+    /// ╎Fish╎15  ╎
+    /// └╌╌╌╌┴╌╌╌╌┘
+    /// ```
+    #[must_use]
+    pub fn dashed() -> Border {
+        box_border('╌', '╎', ('┌', '┐', '└', '┘'), ('┬', '┴', '├', '┤', '┼'))
+    }
+
+    /// Returns an ASCII border that only marks interior crossings.
+    ///
+    /// ```text
+    /// -----------
+    /// |Fish|15  |
+    /// |----+----|
+    /// |Pear|5   |
+    /// -----------
+    /// ```
+    #[must_use]
+    pub fn ascii_compact() -> Border {
+        Border {
+            left_split: '|',
+            right_split: '|',
+            horizontal_split_intersect_both: '+',
+            ..uniform_border('-', '|', ('-', '-', '-', '-'), '-')
+        }
+    }
+
+    /// Returns a border drawn with dots.
+    ///
+    /// ```text
+    /// ...........
+    /// :Fish:15  :
+    /// :....:....:
+    /// ```
+    #[must_use]
+    pub fn dots() -> Border {
+        Border {
+            top_split: '.',
+            ..uniform_border('.', ':', ('.', '.', ':', ':'), ':')
+        }
+    }
+
+    /// Returns a border drawn with spaces, so that only the cells are visible.
+    ///
+    /// The border still takes up its usual space around and between cells;
+    ///  it is simply left blank. To leave out the border lines altogether,
+    ///  use `BorderLines::borderless` instead.
+    #[must_use]
+    pub fn blank() -> Border {
+        uniform_border(' ', ' ', (' ', ' ', ' ', ' '), ' ')
     }
 
-    /// Formats a horizontal split.
+    /// Returns the glyph joining the lines that meet at a point within the
+    ///  table.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The border.
+    /// * `up` - Whether a vertical split meets the point from above.
+    /// * `down` - Whether a vertical split meets the point from below.
+    /// * `left` - Whether a horizontal split meets the point from the left.
+    /// * `right` - Whether a horizontal split meets the point from the right.
     #[must_use]
-    pub fn format_horizontal_split(
+    pub fn junction(
+        self: &Border,
+        up: bool,
+        down: bool,
+        left: bool,
+        right: bool
+    ) -> char {
+        match (up, down, left, right) {
+            (true, true, true, true) => self.horizontal_split_intersect_both,
+            (true, true, true, false) => self.vertical_split_intersect_left,
+            (true, true, false, true) => self.vertical_split_intersect_right,
+            (true, true, false, false) => self.vertical_split,
+            (true, false, _, _) => self.horizontal_split_intersect_top,
+            (false, true, _, _) => self.horizontal_split_intersect_bottom,
+            (false, false, _, _) => self.horizontal_split
+        }
+    }
+
+    /// Returns the color used to draw a part of the border.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The border.
+    /// * `part` - The part of the border.
+    fn part_color(
+        self: &Border,
+        part: BorderPart
+    ) -> Color {
+        let color = match part {
+            BorderPart::Frame => self.frame_color,
+            BorderPart::HeaderSeparator => self.header_separator_color,
+            BorderPart::Grid => self.grid_color
+        };
+        color.unwrap_or(self.color)
+    }
+
+    /// Paints border glyphs in the color of a part of the border.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The border.
+    /// * `glyphs` - The glyphs to paint.
+    /// * `part` - The part of the border the glyphs are in.
+    fn paint(
         self: &Border,
-        widths: &[usize]
+        glyphs: &str,
+        part: BorderPart
     ) -> String {
-        let mut result: String = String::from(self.left_split);
-        for ix in 0..widths.len() {
-            result.push_str(
-                &(0..widths[ix])
-                    .map(|_| self.horizontal_split)
-                    .collect::<String>()
-            );
-            if ix < widths.len() - 1 {
-                result.push_str(
-                    String::from(self.horizontal_split_intersect_both).as_str()
-                );
-            }
+        color::paint(
+            glyphs,
+            self.part_color(part),
+            Layer::Foreground,
+            color_depth()
+        )
+    }
+}
+
+/// A line of border glyphs, possibly interleaved with cell content.
+///
+/// Consecutive glyphs of the same color are painted together so that each
+///  run of border only carries a single set of color escape sequences.
+pub struct BorderLine<'a> {
+    border: &'a Border,
+    result: String,
+    glyphs: String,
+    part: BorderPart
+}
+
+impl<'a> BorderLine<'a> {
+    /// Returns a new, empty border line.
+    ///
+    /// # Arguments
+    ///
+    /// * `border` - The border the glyphs belong to.
+    #[must_use]
+    pub fn new(border: &'a Border) -> BorderLine<'a> {
+        BorderLine {
+            border,
+            result: String::from(""),
+            glyphs: String::from(""),
+            part: BorderPart::Frame
         }
-        result.push_str(String::from(self.right_split).as_str());
-        result
     }
 
-    /// Formats a vertical split.
+    /// Adds a border glyph to the line.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The line.
+    /// * `glyph` - The glyph to add.
+    /// * `part` - The part of the border the glyph is in.
+    pub fn push_glyph(
+        self: &mut BorderLine<'a>,
+        glyph: char,
+        part: BorderPart
+    ) {
+        self.push_glyphs(&glyph.to_string(), part);
+    }
+
+    /// Adds border glyphs to the line.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The line.
+    /// * `glyphs` - The glyphs to add.
+    /// * `part` - The part of the border the glyphs are in.
+    pub fn push_glyphs(
+        self: &mut BorderLine<'a>,
+        glyphs: &str,
+        part: BorderPart
+    ) {
+        if self.border.part_color(part) != self.border.part_color(self.part) {
+            self.flush();
+        }
+        self.part = part;
+        self.glyphs.push_str(glyphs);
+    }
+
+    /// Adds (already formatted) cell content to the line.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The line.
+    /// * `content` - The content to add.
+    pub fn push_content(
+        self: &mut BorderLine<'a>,
+        content: &str
+    ) {
+        self.flush();
+        self.result.push_str(content);
+    }
+
+    /// Returns the formatted line.
     #[must_use]
-    pub fn format_vertical_split(
-        self: &Border
-    ) -> String {
-        format!("{}", self.vertical_split)
+    pub fn finish(mut self: BorderLine<'a>) -> String {
+        self.flush();
+        self.result
+    }
+
+    /// Paints any pending glyphs into the line.
+    fn flush(self: &mut BorderLine<'a>) {
+        if !self.glyphs.is_empty() {
+            self.result.push_str(&self.border.paint(&self.glyphs, self.part));
+            self.glyphs.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::Table;
+    use crate::table::row::Row;
+    use crate::table::cell::Cell;
+    use crate::content::text::strip_escapes;
+
+    #[test]
+    fn presets_are_selectable_by_name() {
+        for name in BORDER_PRESETS.iter() {
+            assert!(name.parse::<Border>().is_ok(), "{}", name);
+        }
+        assert_eq!(
+            "Rounded".parse::<Border>().unwrap().top_left,
+            '╭'
+        );
+        assert_eq!(
+            "ascii_compact".parse::<Border>().unwrap().horizontal_split_intersect_both,
+            '+'
+        );
+        assert_eq!(
+            "wavy".parse::<Border>().unwrap_err().to_string(),
+            "unknown border preset 'wavy' (expected one of: ascii, single, double, heavy, rounded, dashed, ascii-compact, dots, blank)"
+        );
+    }
+
+    #[test]
+    fn presets_draw_their_glyphs() {
+        let mut table = crate::table!(
+            "{}" => "Fish", "{}" => "15";
+            "Pear", "5"
+        );
+
+        table.border = Border::single();
+        assert_eq!(
+            strip_escapes(&table.format()),
+            "┌────┬──┐\n│Fish│15│\n├────┼──┤\n│Pear│5 │\n└────┴──┘\n"
+        );
+
+        table.border = Border::ascii_compact();
+        assert_eq!(
+            strip_escapes(&table.format()),
+            "---------\n|Fish|15|\n|----+--|\n|Pear|5 |\n---------\n"
+        );
+
+        table.border = Border::blank();
+        assert_eq!(
+            strip_escapes(&table.format()),
+            "         \n Fish 15 \n         \n Pear 5  \n         \n"
+        );
+    }
+
+    #[test]
+    fn row_separators_draw_after_every_n_rows() {
+        let drawn = |separators: RowSeparators| {
+            (0..4).map(|ix| separators.draws_after(ix)).collect::<Vec<bool>>()
+        };
+
+        assert_eq!(drawn(RowSeparators::None), [false, false, false, false]);
+        assert_eq!(drawn(RowSeparators::Every), [true, true, true, true]);
+        assert_eq!(drawn(RowSeparators::EveryN(2)), [false, true, false, true]);
+        // A count of zero never draws a separator
+        assert_eq!(drawn(RowSeparators::EveryN(0)), [false, false, false, false]);
+    }
+
+    #[test]
+    fn junction_connects_lines_meeting_at_a_point() {
+        let border = Border {
+            vertical_split: '│',
+            vertical_split_intersect_left: '┤',
+            vertical_split_intersect_right: '├',
+            horizontal_split: '─',
+            horizontal_split_intersect_top: '┴',
+            horizontal_split_intersect_bottom: '┬',
+            horizontal_split_intersect_both: '┼',
+            ..Border::default()
+        };
+
+        assert_eq!(
+            [
+                border.junction(true, true, true, true),
+                border.junction(true, true, true, false),
+                border.junction(true, true, false, true),
+                border.junction(true, true, false, false),
+                border.junction(true, false, true, true),
+                border.junction(false, true, true, true),
+                border.junction(false, false, true, true)
+            ],
+            ['┼', '┤', '├', '│', '┴', '┬', '─']
+        );
     }
 }
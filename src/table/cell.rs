@@ -1,4 +1,4 @@
-use crate::content::{Content, ContentIterator, ContentStyle, CellWidth};
+use crate::content::{Alignment, Content, ContentIterator, ContentStyle, CellWidth};
 use crate::data_item::DataItem;
 use std::clone::Clone;
 
@@ -16,38 +16,32 @@ impl<'a> Iterator for TableCellContentIterator<'a> {
     type Item = String;
 
     fn next(&mut self) -> Option<String> {
-        let line = 
-            if self.current_line_ix < self.content.len() {
-                // Get the next line from the current content iterator
-                self.current_content_iterator.next().map_or_else(|| {
-                    // Go to the next line
-                    self.current_line_ix += 1;
-
-                    // If there are more lines, get iterator for next line
-                    if self.current_line_ix < self.content.len() {
-                        self.current_content_iterator = 
-                            self.content[self.current_line_ix].get_iterator(
-                                &self.base_style.clone(), self.width);
-
-                        // Get the line from the iterator
-                        self.current_content_iterator.next()
-                    } else {
-                        // No more lines to get
-                        None
-                    }
-                }, Some)
-            } else {
-                None
-            };
+        // Get the next line, moving through content lines as each runs out
+        let mut line = None;
+        while line.is_none() && self.current_line_ix < self.content.len() {
+            line = self.current_content_iterator.next();
+            if line.is_none() {
+                // Go to the next line
+                self.current_line_ix += 1;
+
+                // If there are more lines, get iterator for next line
+                if self.current_line_ix < self.content.len() {
+                    self.current_content_iterator =
+                        self.content[self.current_line_ix].get_iterator(
+                            &self.base_style, self.width);
+                }
+            }
+        }
 
         match line {
             Some(content) => {
+                self.current_height += 1;
                 Some(content)
-            } ,
+            },
             None => {
                 if self.current_height < self.target_height {
                     // An empty line of spaces the width of the column
-                    let result = 
+                    let result =
                         (0..self.width)
                             .map(|_| " ")
                             .collect::<String>();
@@ -69,14 +63,43 @@ macro_rules! cell_content {
     };
 }
 
+/// Builds a cell from a style directive for each content line and the
+///  content lines themselves.
+///
+/// Style directives given as string literals are checked when the code is
+///  compiled, so a malformed directive is a compile error:
+///
+/// ```
+/// use libtableformat::cell;
+/// use libtableformat::table::cell::Cell;
+///
+/// let cell = cell!("{^:10:} {>}", "Fish", "15");
+/// ```
+///
+/// ```compile_fail
+/// use libtableformat::cell;
+/// use libtableformat::table::cell::Cell;
+///
+/// let cell = cell!("{^:10:} {>x}", "Fish", "15");
+/// ```
 #[allow(unused_macros)]
 #[macro_export]
 macro_rules! cell {
+    ($style:literal, $($content:tt)*) => {
+        {
+            // Reject malformed directives at compile time
+            const _: () = $crate::ContentStyle::assert_valid_directives($style);
+            Cell::from_styled_content(
+                $style,
+                $crate::cell_content!($($content)*)
+            )
+        }
+    };
     ($style:expr, $($content:tt)*) => {
         Cell::from_styled_content(
             $style, 
-            crate::cell_content!($($content)*)
-        );
+            $crate::cell_content!($($content)*)
+        )
     }
 }
 
@@ -87,6 +110,8 @@ macro_rules! cell {
 pub struct Cell {
     contents: Vec<Content>,
     base_style: ContentStyle,
+    column_span: usize,
+    row_span: usize,
 }
 
 impl Cell {
@@ -95,6 +120,8 @@ impl Cell {
         Cell {
             contents: Vec::new(),
             base_style: ContentStyle::default(),
+            column_span: 1,
+            row_span: 1,
         }
     }
 
@@ -105,10 +132,57 @@ impl Cell {
     ) -> Cell {
         Cell {
             contents,
-            base_style
+            base_style,
+            column_span: 1,
+            row_span: 1,
         }
     }
 
+    /// Returns the number of columns this cell spans.
+    #[must_use]
+    pub fn column_span(
+        self: &Cell
+    ) -> usize {
+        self.column_span
+    }
+
+    /// Sets the number of columns this cell spans.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table cell.
+    /// * `column_span` - The number of columns to span (at least one).
+    pub fn set_column_span(
+        self: &mut Cell,
+        column_span: usize
+    ) {
+        self.column_span = column_span.max(1);
+    }
+
+    /// Returns the number of rows this cell spans.
+    #[must_use]
+    pub fn row_span(
+        self: &Cell
+    ) -> usize {
+        self.row_span
+    }
+
+    /// Sets the number of rows this cell spans.
+    ///
+    /// A cell spanning rows covers the same columns in the rows beneath it.
+    ///  Those rows should not include cells for the covered columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table cell.
+    /// * `row_span` - The number of rows to span (at least one).
+    pub fn set_row_span(
+        self: &mut Cell,
+        row_span: usize
+    ) {
+        self.row_span = row_span.max(1);
+    }
+
     /// Returns a `Cell` from styled content.
     ///
     /// # Arguments
@@ -157,7 +231,7 @@ impl Cell {
     }
 
     /// Returns the column break specified in the first content line of the
-    /// cell.
+    /// cell, or in its base style if the line has no style of its own.
     ///
     /// This is used to determine the column break for cells used in the table
     /// header row.
@@ -170,11 +244,52 @@ impl Cell {
         else {
             match &self.contents[0].style {
                 Some(style) => style.width.clone(),
-                None => CellWidth::default()
+                None => self.base_style.width.clone()
             }
         }
     }
 
+    /// Returns the unformatted text of each content line of this cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table cell.
+    pub fn lines(
+        self: &Cell
+    ) -> impl Iterator<Item=&str> + '_ {
+        self.contents.iter().map(Content::text)
+    }
+
+    /// Returns the unformatted text of each content line of this cell along
+    ///  with the style it is formatted with.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table cell.
+    pub fn styled_lines(
+        self: &Cell
+    ) -> impl Iterator<Item=(&str, &ContentStyle)> + '_ {
+        self.contents.iter()
+            .map(move |content|
+                (content.text(), content.resolve_style(&self.base_style)))
+    }
+
+    /// Returns the alignment of the first content line of this cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table cell.
+    #[must_use]
+    pub fn alignment(
+        self: &Cell
+    ) -> Alignment {
+        match self.contents.first() {
+            Some(content) =>
+                content.resolve_style(&self.base_style).alignment.clone(),
+            None => self.base_style.alignment.clone()
+        }
+    }
+
     /// Returns the next formatted line of content from this table cell.
     ///
     /// # Arguments
@@ -185,14 +300,21 @@ impl Cell {
     pub fn get_iterator(
         self: &Cell,
         column_break: &CellWidth
-    ) -> TableCellContentIterator {
+    ) -> TableCellContentIterator<'_> {
         // Determine the render width of this cell
         let cell_width = self.measure_width(column_break);
 
+        // Empty cells start with an exhausted iterator and are padded out
+        let current_content_iterator =
+            if self.contents.is_empty() {
+                ContentIterator::new(Vec::new())
+            } else {
+                self.contents[0].get_iterator(&self.base_style, cell_width)
+            };
+
         TableCellContentIterator {
             content: &self.contents,
-            current_content_iterator: 
-                self.contents[0].get_iterator(&self.base_style.clone(), cell_width),
+            current_content_iterator,
             current_line_ix: 0,
             base_style: self.base_style.clone(),
             width: cell_width,
@@ -213,20 +335,12 @@ impl Cell {
         self: &Cell,
         column_break: &CellWidth,
     ) -> usize {
-        let mut height = 0;
-
         // Determine the render width of this cell
         let cell_width = self.measure_width(column_break);
 
+        let mut height = 0;
         for line in &self.contents {
-            let line_width = line.measure_width();
-            // If line fits within column then line height is 1
-            if !line.will_wrap() || (line_width <= cell_width) {
-                height += 1
-            } else {
-                // Determine how many lines are needed when content is wrapped
-                height += line_width.div_euclid(cell_width) + 1;
-            }
+            height += line.measure_height(&self.base_style, cell_width);
         }
 
         height
@@ -0,0 +1,473 @@
+use std::io::{Read, Write};
+use super::Table;
+use super::cell::Cell;
+use super::row::Row;
+use crate::content::{Alignment, ContentStyle};
+use crate::content::text::strip_escapes;
+use crate::data_item::DataItem;
+
+/// Options for writing a table as delimited text (such as CSV).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelimitedOptions {
+    /// The character separating fields.
+    pub delimiter: char,
+    /// The text joining the lines of multi-line cells.
+    pub line_separator: String,
+}
+
+impl DelimitedOptions {
+    /// Returns options for comma-separated values.
+    #[must_use]
+    pub fn csv() -> DelimitedOptions {
+        DelimitedOptions {
+            delimiter: ',',
+            line_separator: String::from("\n"),
+        }
+    }
+
+    /// Returns options for tab-separated values.
+    #[must_use]
+    pub fn tsv() -> DelimitedOptions {
+        DelimitedOptions {
+            delimiter: '\t',
+            ..DelimitedOptions::csv()
+        }
+    }
+}
+
+impl Default for DelimitedOptions {
+    fn default() -> Self {
+        Self::csv()
+    }
+}
+
+/// Options for reading a table from CSV.
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// The character separating fields.
+    pub delimiter: char,
+    /// The character used to quote fields.
+    pub quote: char,
+    /// Whether the first record holds the column headers.
+    pub has_header: bool,
+    /// The base style of the cells in each column (the default style is
+    ///  used for columns without a style).
+    pub column_styles: Vec<ContentStyle>,
+    /// Whether columns whose values are all numbers are right-aligned.
+    pub align_numbers: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: ',',
+            quote: '"',
+            has_header: true,
+            column_styles: Vec::new(),
+            align_numbers: false,
+        }
+    }
+}
+
+impl Table {
+    /// Returns a table read from CSV.
+    ///
+    /// The header record (if any) becomes the column headers and the
+    ///  remaining records become the data rows, built through
+    ///  `from_data_source`. Fields containing line breaks become multi-line
+    ///  cells. Records shorter than the widest record are padded with empty
+    ///  fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader providing the CSV text.
+    /// * `options` - The CSV dialect and column styles.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the reader, or an `InvalidData` error if the
+    ///  text is not valid UTF-8 or a quoted field is not terminated.
+    pub fn from_csv<R: Read>(
+        mut reader: R,
+        options: &CsvOptions
+    ) -> std::io::Result<Table> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+
+        let mut records = parse_records(&text, options)?.into_iter();
+        let header = if options.has_header { records.next() } else { None };
+        let records: Vec<Vec<String>> = records.collect();
+
+        let column_count = header.iter()
+            .chain(records.iter())
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0);
+
+        // Resolve the style of each column
+        let column_styles: Vec<ContentStyle> = (0..column_count)
+            .map(|column_ix| {
+                let mut style = options.column_styles.get(column_ix)
+                    .cloned()
+                    .unwrap_or_default();
+                if options.align_numbers && is_numeric_column(&records, column_ix) {
+                    style.alignment = Alignment::Right;
+                }
+                style
+            })
+            .collect();
+
+        // Header cells describe the columns, even if there is no header
+        let header_cells: Vec<Cell> = (0..column_count)
+            .map(|column_ix| {
+                let text = header.as_ref()
+                    .and_then(|header| header.get(column_ix))
+                    .map_or("", String::as_str);
+                Cell::from_data_item(
+                    &DataItem::from_text(text),
+                    column_styles[column_ix].clone()
+                )
+            })
+            .collect();
+
+        let rows = records.iter()
+            .map(|record| (0..column_count)
+                .map(|column_ix| DataItem::from_text(
+                    record.get(column_ix).map_or("", String::as_str)))
+                .collect());
+
+        let mut table = Table::from_data_source(
+            Row::from(header_cells),
+            &column_styles,
+            Vec::new(),
+            rows
+        );
+        if header.is_none() {
+            table.column_headers = Row::new();
+        }
+
+        Ok(table)
+    }
+
+    /// Writes the contents of the table as comma-separated values.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table to write.
+    /// * `writer` - The writer to write to.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the writer.
+    pub fn write_csv<W: Write>(
+        self: &Table,
+        writer: &mut W
+    ) -> std::io::Result<()> {
+        self.write_delimited(writer, &DelimitedOptions::csv())
+    }
+
+    /// Writes the contents of the table as tab-separated values.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table to write.
+    /// * `writer` - The writer to write to.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the writer.
+    pub fn write_tsv<W: Write>(
+        self: &Table,
+        writer: &mut W
+    ) -> std::io::Result<()> {
+        self.write_delimited(writer, &DelimitedOptions::tsv())
+    }
+
+    /// Writes the contents of the table as delimited text.
+    ///
+    /// The first record holds the column headers, if any (preceded by the row
+    ///  header corner if the table has row headers) and each data row
+    ///  follows as a record. Styling is dropped. Fields are quoted following
+    ///  RFC 4180 and records end with CRLF. Positions covered by a spanning
+    ///  cell are written as empty fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table to write.
+    /// * `writer` - The writer to write to.
+    /// * `options` - The delimiter and multi-line cell separator.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the writer.
+    pub fn write_delimited<W: Write>(
+        self: &Table,
+        writer: &mut W,
+        options: &DelimitedOptions
+    ) -> std::io::Result<()> {
+        let empty = Cell::empty();
+        let grid = self.build_grid(&empty);
+
+        // The column headers are the last row of the header section
+        let header_rows = grid.section_rows(0);
+        let rows = (header_rows.end.saturating_sub(1)..header_rows.end)
+            .chain(grid.section_rows(1));
+
+        for row_ix in rows {
+            let fields: Vec<String> = grid.row(row_ix).iter()
+                .map(|placed| placed.map_or_else(
+                    String::new,
+                    |(cell, _, _)| quote(&cell_text(cell, options), options)))
+                .collect();
+            write!(
+                writer,
+                "{}\r\n",
+                fields.join(&options.delimiter.to_string())
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses delimited text into records of fields.
+///
+/// Quoted fields may contain delimiters, line breaks and doubled quotes.
+///  Records end with LF or CRLF, and a final line break is optional. Blank
+///  lines are skipped.
+///
+/// # Arguments
+///
+/// * `text` - The text to parse.
+/// * `options` - The delimiter and quote characters.
+fn parse_records(
+    text: &str,
+    options: &CsvOptions
+) -> std::io::Result<Vec<Vec<String>>> {
+    let mut records: Vec<Vec<String>> = Vec::new();
+    let mut record: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == options.quote {
+                if chars.peek() == Some(&options.quote) {
+                    field.push(c);
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == options.quote && field.is_empty() {
+            in_quotes = true;
+        } else if c == options.delimiter {
+            record.push(std::mem::take(&mut field));
+        } else if c == '\n' || (c == '\r' && chars.peek() == Some(&'\n')) {
+            if c == '\r' {
+                chars.next();
+            }
+            record.push(std::mem::take(&mut field));
+            if record.len() == 1 && record[0].is_empty() {
+                record.clear();
+            } else {
+                records.push(std::mem::take(&mut record));
+            }
+        } else {
+            field.push(c);
+        }
+    }
+
+    if in_quotes {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "unterminated quoted field"
+        ));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+/// Returns whether all non-empty values of a column are numbers (and the
+///  column has at least one value).
+///
+/// # Arguments
+///
+/// * `records` - The data records.
+/// * `column_ix` - The index of the column.
+fn is_numeric_column(
+    records: &[Vec<String>],
+    column_ix: usize
+) -> bool {
+    let mut values = records.iter()
+        .filter_map(|record| record.get(column_ix))
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .peekable();
+
+    values.peek().is_some()
+        && values.all(|value|
+            value.parse::<f64>().is_ok()
+                && value.chars().any(|c| c.is_ascii_digit()))
+}
+
+/// Returns the unstyled text of a cell.
+///
+/// # Arguments
+///
+/// * `cell` - The table cell.
+/// * `options` - The options containing the multi-line cell separator.
+fn cell_text(
+    cell: &Cell,
+    options: &DelimitedOptions
+) -> String {
+    cell.lines()
+        .map(strip_escapes)
+        .collect::<Vec<String>>()
+        .join(&options.line_separator)
+}
+
+/// Quotes a field if it contains the delimiter, a quote or a line break.
+///
+/// # Arguments
+///
+/// * `field` - The field to quote.
+/// * `options` - The options containing the delimiter.
+fn quote(
+    field: &str,
+    options: &DelimitedOptions
+) -> String {
+    let needs_quotes = field.chars()
+        .any(|c| c == options.delimiter || c == '"' || c == '\r' || c == '\n');
+    if needs_quotes {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::Border;
+    use crate::content::CellWidth;
+    use crate::content::text::strip_escapes;
+
+    #[test]
+    fn csv_quotes_fields_and_joins_lines() {
+        let table = Table::new(
+            Border::default(),
+            vec!(CellWidth::Content, CellWidth::Content),
+            crate::row!("{r}" => "Name", "{}" => "Quote"),
+            Vec::new(),
+            vec!(
+                crate::row!("{}", "Smith, J", "say \"hi\""),
+                Row::from(vec!(
+                    crate::cell!("{}", "Lee"),
+                    Cell::from_styled_content(
+                        "{} {}",
+                        vec!("\u{1b}[32mfirst\u{1b}[0m", "second")
+                    )
+                ))
+            )
+        );
+
+        let mut csv: Vec<u8> = Vec::new();
+        table.write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "Name,Quote\r\n\"Smith, J\",\"say \"\"hi\"\"\"\r\nLee,\"first\nsecond\"\r\n"
+        );
+
+        let mut tsv: Vec<u8> = Vec::new();
+        table.write_delimited(&mut tsv, &DelimitedOptions {
+            line_separator: String::from(" / "),
+            ..DelimitedOptions::tsv()
+        }).unwrap();
+        assert_eq!(
+            String::from_utf8(tsv).unwrap(),
+            "Name\tQuote\r\nSmith, J\t\"say \"\"hi\"\"\"\r\nLee\tfirst / second\r\n"
+        );
+    }
+
+    #[test]
+    fn from_csv_reads_headers_and_aligns_numbers() {
+        let csv = "Item;Price\r\nFish;5\r\n'Steak; rare';12.50\r\n";
+        let table = Table::from_csv(
+            csv.as_bytes(),
+            &CsvOptions {
+                delimiter: ';',
+                quote: '\'',
+                align_numbers: true,
+                ..CsvOptions::default()
+            }
+        ).unwrap();
+
+        let expected = "+-----------+-----+\n|Item       |Price|\n+-----------+-----+\n|Fish       |    5|\n+-----------+-----+\n|Steak; rare|12.50|\n+-----------+-----+\n";
+
+        assert_eq!(
+            strip_escapes(&table.format()),
+            expected
+        );
+        assert!(Table::from_csv("\"open".as_bytes(), &CsvOptions::default())
+            .is_err());
+    }
+
+    #[test]
+    fn from_csv_renders_empty_fields_as_blank_lines() {
+        let table = Table::from_csv(
+            "a,b\n,\nx,y\n".as_bytes(),
+            &CsvOptions::default()
+        ).unwrap();
+
+        let expected = "+-+-+\n|a|b|\n+-+-+\n| | |\n+-+-+\n|x|y|\n+-+-+\n";
+
+        assert_eq!(
+            strip_escapes(&table.format()),
+            expected
+        );
+    }
+
+    #[test]
+    fn from_csv_applies_column_style_widths() {
+        let table = Table::from_csv(
+            "Item,Price\nFish,5\n".as_bytes(),
+            &CsvOptions {
+                column_styles: vec!(
+                    ContentStyle::from_format("{:6:}"),
+                    ContentStyle::from_format("{>:7:}")
+                ),
+                ..CsvOptions::default()
+            }
+        ).unwrap();
+
+        let expected = "+------+-------+\n|Item  |  Price|\n+------+-------+\n|Fish  |      5|\n+------+-------+\n";
+
+        assert_eq!(
+            strip_escapes(&table.format()),
+            expected
+        );
+    }
+
+    #[test]
+    fn from_csv_skips_blank_lines() {
+        let table = Table::from_csv(
+            "a,b\n\n1,2\r\n\r\n".as_bytes(),
+            &CsvOptions::default()
+        ).unwrap();
+
+        let expected = "+-+-+\n|a|b|\n+-+-+\n|1|2|\n+-+-+\n";
+
+        assert_eq!(
+            strip_escapes(&table.format()),
+            expected
+        );
+    }
+}
@@ -0,0 +1,498 @@
+use super::border::{Border, BorderLine, BorderLines, BorderPart};
+use super::cell::{Cell, TableCellContentIterator};
+use crate::content::CellWidth;
+
+/// A cell placed into the grid of a table.
+struct GridCell<'a> {
+    cell: &'a Cell,
+    row: usize,
+    column: usize,
+    row_span: usize,
+    column_span: usize,
+}
+
+/// The grid of cells that make up a formatted table.
+///
+/// The grid resolves which cell covers each row and column position, taking
+///  into account cells that span multiple columns or rows, and renders the
+///  cells along with the borders between them.
+pub struct Grid<'a> {
+    column_count: usize,
+    cells: Vec<GridCell<'a>>,
+    slots: Vec<Vec<Option<usize>>>,
+    section_starts: Vec<usize>,
+}
+
+impl<'a> Grid<'a> {
+    /// Returns a grid with cells placed into rows.
+    ///
+    /// Cells are placed left to right into the first column not already
+    ///  covered by a cell spanning down from an earlier row. Row spans do not
+    ///  extend beyond the end of the section the row belongs to.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_count` - The number of columns in the grid.
+    /// * `sections` - The sections of the table (such as the header and the
+    ///   body), each containing rows of cells.
+    pub fn new(
+        column_count: usize,
+        sections: Vec<Vec<Vec<&'a Cell>>>
+    ) -> Grid<'a> {
+        let mut cells: Vec<GridCell<'a>> = Vec::new();
+        let mut slots: Vec<Vec<Option<usize>>> = Vec::new();
+        let mut section_starts: Vec<usize> = Vec::new();
+
+        for section in sections {
+            let section_start = slots.len();
+            section_starts.push(section_start);
+            let section_end = section_start + section.len();
+            slots.resize(section_end, vec!(None; column_count));
+
+            for (section_row_ix, row) in section.into_iter().enumerate() {
+                let row_ix = section_start + section_row_ix;
+                let mut column_ix = 0;
+                for cell in row {
+                    // Skip columns covered by cells from earlier rows
+                    while column_ix < column_count
+                        && slots[row_ix][column_ix].is_some() {
+                        column_ix += 1;
+                    }
+                    if column_ix >= column_count {
+                        break;
+                    }
+
+                    // Spans stop at covered columns and the section end
+                    let column_span = (column_ix..column_ix + cell.column_span())
+                        .take_while(|ix| *ix < column_count
+                            && slots[row_ix][*ix].is_none())
+                        .count();
+                    let row_span =
+                        cell.row_span().min(section_end - row_ix);
+
+                    let cell_ix = cells.len();
+                    for slot_row in &mut slots[row_ix..row_ix + row_span] {
+                        for slot in
+                            &mut slot_row[column_ix..column_ix + column_span] {
+                            *slot = Some(cell_ix);
+                        }
+                    }
+                    cells.push(GridCell {
+                        cell,
+                        row: row_ix,
+                        column: column_ix,
+                        row_span,
+                        column_span,
+                    });
+                    column_ix += column_span;
+                }
+            }
+        }
+
+        Grid {
+            column_count,
+            cells,
+            slots,
+            section_starts,
+        }
+    }
+
+    /// Returns each placed cell with its starting column and column span.
+    pub fn placements(
+        self: &Grid<'a>
+    ) -> impl Iterator<Item=(usize, usize, &'a Cell)> + '_ {
+        self.cells.iter()
+            .map(|grid_cell|
+                (grid_cell.column, grid_cell.column_span, grid_cell.cell))
+    }
+
+    /// Returns the number of columns in the grid.
+    pub fn column_count(
+        self: &Grid<'a>
+    ) -> usize {
+        self.column_count
+    }
+
+    /// Returns the rows belonging to a section of the grid.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The grid.
+    /// * `section_ix` - The index of the section.
+    pub fn section_rows(
+        self: &Grid<'a>,
+        section_ix: usize
+    ) -> std::ops::Range<usize> {
+        let start = self.section_starts[section_ix];
+        let end = self.section_starts.get(section_ix + 1)
+            .copied()
+            .unwrap_or(self.slots.len());
+        start..end
+    }
+
+    /// Returns the cells of a row, by column.
+    ///
+    /// Each cell is returned in the column it starts in, with its row span
+    ///  and column span (clipped to the grid). Columns covered by a cell
+    ///  starting in another row or column, and columns without a cell, are
+    ///  `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The grid.
+    /// * `row_ix` - The index of the row.
+    pub fn row(
+        self: &Grid<'a>,
+        row_ix: usize
+    ) -> Vec<Option<(&'a Cell, usize, usize)>> {
+        self.slots[row_ix].iter()
+            .enumerate()
+            .map(|(column_ix, slot)| slot
+                .map(|cell_ix| &self.cells[cell_ix])
+                .filter(|grid_cell|
+                    grid_cell.row == row_ix && grid_cell.column == column_ix)
+                .map(|grid_cell|
+                    (grid_cell.cell, grid_cell.row_span, grid_cell.column_span)))
+            .collect()
+    }
+
+    /// Formats the grid, including its borders.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The grid to format.
+    /// * `border` - The table border.
+    /// * `lines` - The border lines to draw.
+    /// * `widths` - The width of each column.
+    pub fn format(
+        self: &Grid<'a>,
+        border: &Border,
+        lines: &BorderLines,
+        widths: &[usize]
+    ) -> String {
+        let mut result: String = String::from("");
+
+        if self.slots.is_empty() {
+            return result;
+        }
+        let last_row_ix = self.slots.len() - 1;
+
+        let frame = Frame { border, lines, widths };
+        let heights = self.measure_row_heights(&frame);
+        let mut iterators: Vec<TableCellContentIterator> =
+            self.cells.iter()
+                .map(|grid_cell| grid_cell.cell.get_iterator(
+                    &CellWidth::Fixed(self.cell_width(grid_cell, &frame))))
+                .collect();
+
+        // Print top border
+        if lines.outer_frame {
+            result.push_str(&self.format_outer_line(&frame, 0, true));
+            result.push('\n');
+        }
+
+        for (row_ix, height) in heights.iter().enumerate() {
+            for _line_ix in 0..*height {
+                result.push_str(
+                    &self.format_content_line(&frame, row_ix, &mut iterators));
+                result.push('\n');
+            }
+            if row_ix < last_row_ix && self.draws_split(lines, row_ix) {
+                result.push_str(
+                    &self.format_split_line(&frame, row_ix, &mut iterators));
+                result.push('\n');
+            }
+        }
+
+        // Print bottom border
+        if lines.outer_frame {
+            result.push_str(
+                &self.format_outer_line(&frame, last_row_ix, false));
+            result.push('\n');
+        }
+
+        result
+    }
+
+    /// Formats the top border above the first row or the bottom border
+    ///  beneath the last row.
+    fn format_outer_line(
+        self: &Grid<'a>,
+        frame: &Frame,
+        row_ix: usize,
+        top: bool
+    ) -> String {
+        let border = frame.border;
+        let (left, fill, right, split) =
+            if top {
+                (border.top_left, border.top, border.top_right, border.top_split)
+            } else {
+                (border.bottom_left, border.bottom, border.bottom_right,
+                    border.bottom_split)
+            };
+
+        let mut line = BorderLine::new(border);
+        line.push_glyph(left, BorderPart::Frame);
+        for (column_ix, width) in frame.widths.iter().enumerate() {
+            line.push_glyphs(&repeat(fill, *width), BorderPart::Frame);
+            if column_ix + 1 < frame.widths.len() {
+                let splits =
+                    !same_cell(
+                        self.slots[row_ix][column_ix],
+                        self.slots[row_ix][column_ix + 1]);
+                let junction =
+                    if !frame.lines.vertical_splits {
+                        repeat(fill, frame.lines.column_gap)
+                    } else if splits {
+                        split.to_string()
+                    } else {
+                        fill.to_string()
+                    };
+                line.push_glyphs(&junction, BorderPart::Frame);
+            }
+        }
+        line.push_glyph(right, BorderPart::Frame);
+        line.finish()
+    }
+
+    /// Formats a line of content within a row.
+    fn format_content_line(
+        self: &Grid<'a>,
+        frame: &Frame,
+        row_ix: usize,
+        iterators: &mut [TableCellContentIterator]
+    ) -> String {
+        let (border, lines, widths) = (frame.border, frame.lines, frame.widths);
+        let mut line = BorderLine::new(border);
+        if lines.outer_frame {
+            line.push_glyph(border.left, BorderPart::Frame);
+        }
+
+        let mut column_ix = 0;
+        while column_ix < widths.len() {
+            let next_column_ix = match self.slots[row_ix][column_ix] {
+                Some(cell_ix) => {
+                    let grid_cell = &self.cells[cell_ix];
+                    line.push_content(&next_line(
+                        &mut iterators[cell_ix],
+                        self.cell_width(grid_cell, frame)
+                    ));
+                    column_ix + grid_cell.column_span
+                },
+                None => {
+                    line.push_content(&blank(widths[column_ix]));
+                    column_ix + 1
+                }
+            };
+            column_ix = next_column_ix;
+            if column_ix < widths.len() {
+                if lines.vertical_splits {
+                    line.push_glyph(border.vertical_split, BorderPart::Grid);
+                } else {
+                    line.push_content(&blank(lines.column_gap));
+                }
+            }
+        }
+
+        if lines.outer_frame {
+            line.push_glyph(border.right, BorderPart::Frame);
+        }
+        line.finish()
+    }
+
+    /// Formats the line between a row and the row beneath it.
+    ///
+    /// Cells spanning both rows continue their content through the line. The
+    ///  lines within the header section and between the header and the body
+    ///  are drawn as the header separator.
+    fn format_split_line(
+        self: &Grid<'a>,
+        frame: &Frame,
+        row_ix: usize,
+        iterators: &mut [TableCellContentIterator]
+    ) -> String {
+        let (border, lines, widths) = (frame.border, frame.lines, frame.widths);
+        let above = &self.slots[row_ix];
+        let below = &self.slots[row_ix + 1];
+        let column_count = widths.len();
+        let part =
+            if self.in_header(row_ix) {
+                BorderPart::HeaderSeparator
+            } else {
+                BorderPart::Grid
+            };
+
+        let mut line = BorderLine::new(border);
+
+        // Left edge continues if the first column spans both rows
+        if lines.outer_frame {
+            line.push_glyph(
+                if same_cell(above[0], below[0]) { border.left }
+                else { border.left_split },
+                BorderPart::Frame);
+        }
+
+        let mut column_ix = 0;
+        while column_ix < column_count {
+            let next_column_ix = match above[column_ix] {
+                Some(cell_ix) if same_cell(above[column_ix], below[column_ix]) => {
+                    let grid_cell = &self.cells[cell_ix];
+                    line.push_content(&next_line(
+                        &mut iterators[cell_ix],
+                        self.cell_width(grid_cell, frame)
+                    ));
+                    column_ix + grid_cell.column_span
+                },
+                _ => {
+                    line.push_glyphs(
+                        &repeat(border.horizontal_split, widths[column_ix]),
+                        part
+                    );
+                    column_ix + 1
+                }
+            };
+            column_ix = next_column_ix;
+            if column_ix < column_count {
+                let (left, right) = (column_ix - 1, column_ix);
+                let (up, down) = (
+                    !same_cell(above[left], above[right]),
+                    !same_cell(below[left], below[right])
+                );
+                let (from_left, from_right) = (
+                    !same_cell(above[left], below[left]),
+                    !same_cell(above[right], below[right])
+                );
+                if lines.vertical_splits {
+                    line.push_glyph(
+                        border.junction(up, down, from_left, from_right),
+                        part);
+                } else if from_left && from_right {
+                    line.push_glyphs(
+                        &repeat(border.horizontal_split, lines.column_gap),
+                        part);
+                } else {
+                    line.push_content(&blank(lines.column_gap));
+                }
+            }
+        }
+
+        // Right edge continues if the last column spans both rows
+        if lines.outer_frame {
+            let last = column_count - 1;
+            line.push_glyph(
+                if same_cell(above[last], below[last]) { border.right }
+                else { border.right_split },
+                BorderPart::Frame);
+        }
+
+        line.finish()
+    }
+
+    /// Returns whether a row is in the header section of the grid (the first
+    ///  of several sections).
+    fn in_header(
+        self: &Grid<'a>,
+        row_ix: usize
+    ) -> bool {
+        self.section_starts.len() > 1 && row_ix < self.section_starts[1]
+    }
+
+    /// Returns whether the split line beneath a row is drawn.
+    fn draws_split(
+        self: &Grid<'a>,
+        lines: &BorderLines,
+        row_ix: usize
+    ) -> bool {
+        if self.in_header(row_ix) {
+            return lines.header_separator;
+        }
+        let section_start = self.section_starts.iter()
+            .rev()
+            .find(|start| **start <= row_ix)
+            .copied()
+            .unwrap_or(0);
+        lines.row_separators.draws_after(row_ix - section_start)
+    }
+
+    /// Returns the width of a cell, including the splits it spans.
+    fn cell_width(
+        self: &Grid<'a>,
+        grid_cell: &GridCell,
+        frame: &Frame
+    ) -> usize {
+        let columns =
+            &frame.widths[grid_cell.column..grid_cell.column + grid_cell.column_span];
+        columns.iter().sum::<usize>()
+            + (grid_cell.column_span - 1) * frame.lines.split_width()
+    }
+
+    /// Measures the height of each row.
+    ///
+    /// Rows are as tall as their tallest single-row cell. A cell spanning
+    ///  several rows can also use the split lines drawn between them and, if
+    ///  it still does not fit, makes the last row it spans taller.
+    fn measure_row_heights(
+        self: &Grid<'a>,
+        frame: &Frame
+    ) -> Vec<usize> {
+        let mut heights = vec!(0; self.slots.len());
+
+        let cell_height = |grid_cell: &GridCell| {
+            grid_cell.cell.measure_height(
+                &CellWidth::Fixed(self.cell_width(grid_cell, frame)))
+        };
+
+        for grid_cell in self.cells.iter().filter(|c| c.row_span == 1) {
+            heights[grid_cell.row] =
+                heights[grid_cell.row].max(cell_height(grid_cell));
+        }
+
+        for grid_cell in self.cells.iter().filter(|c| c.row_span > 1) {
+            let rows = grid_cell.row..grid_cell.row + grid_cell.row_span;
+            let split_lines = (rows.start..rows.end - 1)
+                .filter(|row_ix| self.draws_split(frame.lines, *row_ix))
+                .count();
+            let available =
+                heights[rows.clone()].iter().sum::<usize>() + split_lines;
+            let needed = cell_height(grid_cell);
+            if needed > available {
+                heights[rows.end - 1] += needed - available;
+            }
+        }
+
+        heights
+    }
+}
+
+/// The border and column widths used to format a grid.
+struct Frame<'b> {
+    border: &'b Border,
+    lines: &'b BorderLines,
+    widths: &'b [usize],
+}
+
+/// Returns whether two slots are covered by the same cell.
+fn same_cell(
+    first: Option<usize>,
+    second: Option<usize>
+) -> bool {
+    first.is_some() && first == second
+}
+
+/// Returns the next line of a cell, or blank space once it is exhausted.
+fn next_line(
+    iterator: &mut TableCellContentIterator,
+    width: usize
+) -> String {
+    iterator.next().unwrap_or_else(|| blank(width))
+}
+
+/// Returns a blank line of a given width.
+fn blank(width: usize) -> String {
+    repeat(' ', width)
+}
+
+/// Returns a glyph repeated a number of times.
+fn repeat(glyph: char, count: usize) -> String {
+    (0..count).map(|_| glyph).collect::<String>()
+}
@@ -0,0 +1,358 @@
+use colored::Color;
+use super::Table;
+use super::cell::Cell;
+use crate::content::{Alignment, ContentStyle, TextAttributes};
+use crate::content::color::rgb;
+use crate::content::text::strip_escapes;
+
+/// How content styles are written into HTML output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HtmlStyle {
+    /// Colors, text attributes and alignment are written as inline `style`
+    ///  attributes.
+    Inline,
+    /// Colors, text attributes and alignment are written as CSS classes
+    ///  starting with a prefix (such as `tf-fg-red`, `tf-bold` or
+    ///  `tf-align-center` for the prefix `tf-`). Colors without a class
+    ///  name (RGB colors) are written inline.
+    Classes(String)
+}
+
+impl Table {
+    /// Returns the table formatted as an HTML table, with inline styles.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table to format.
+    #[must_use]
+    pub fn to_html(self: &Table) -> String {
+        self.to_html_with(&HtmlStyle::Inline)
+    }
+
+    /// Returns the table formatted as an HTML table.
+    ///
+    /// The column groups and column headers form the `<thead>` and the data
+    ///  rows form the `<tbody>`. Column headers and row headers are written
+    ///  as `<th>` cells. Spanning cells carry their spans as `colspan` and
+    ///  `rowspan`. The lines of multi-line cells are separated by `<br>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table to format.
+    /// * `style` - How content styles are written.
+    #[must_use]
+    pub fn to_html_with(
+        self: &Table,
+        style: &HtmlStyle
+    ) -> String {
+        let empty = Cell::empty();
+        let grid = self.build_grid(&empty);
+        let has_row_headers = !self.row_headers.is_empty();
+        let mut result: String = String::from("<table>\n");
+
+        let header_rows = grid.section_rows(0);
+        if !header_rows.is_empty() {
+            result.push_str("<thead>\n");
+            for row_ix in header_rows {
+                result.push_str(&format_row(
+                    &grid.row(row_ix), style, |_| "th"));
+            }
+            result.push_str("</thead>\n");
+        }
+
+        result.push_str("<tbody>\n");
+        for row_ix in grid.section_rows(1) {
+            result.push_str(&format_row(
+                &grid.row(row_ix),
+                style,
+                |column_ix| if has_row_headers && column_ix == 0 { "th" }
+                    else { "td" }));
+        }
+        result.push_str("</tbody>\n");
+
+        result.push_str("</table>\n");
+        result
+    }
+}
+
+/// Formats a row of an HTML table.
+///
+/// # Arguments
+///
+/// * `cells` - The cells of the row, by column, with their spans.
+/// * `style` - How content styles are written.
+/// * `tag` - Returns the tag for a cell starting in a column.
+fn format_row<F>(
+    cells: &[Option<(&Cell, usize, usize)>],
+    style: &HtmlStyle,
+    tag: F
+) -> String
+    where
+        F: Fn(usize) -> &'static str
+{
+    let mut result = String::from("<tr>");
+    for (column_ix, placed) in cells.iter().enumerate() {
+        if let Some((cell, row_span, column_span)) = placed {
+            result.push_str(&format_cell(
+                cell, tag(column_ix), (*row_span, *column_span), style));
+        }
+    }
+    result.push_str("</tr>\n");
+    result
+}
+
+/// Formats a cell of an HTML table.
+///
+/// The cell is styled using its first content line. Following lines with
+///  different colors are wrapped in a `<span>`.
+///
+/// # Arguments
+///
+/// * `cell` - The table cell.
+/// * `tag` - The tag of the cell (`th` or `td`).
+/// * `(row_span, column_span)` - The number of rows and columns spanned.
+/// * `style` - How content styles are written.
+fn format_cell(
+    cell: &Cell,
+    tag: &str,
+    (row_span, column_span): (usize, usize),
+    style: &HtmlStyle
+) -> String {
+    let lines: Vec<(&str, &ContentStyle)> = cell.styled_lines().collect();
+
+    let mut result = format!("<{}", tag);
+    if column_span > 1 {
+        result.push_str(&format!(" colspan=\"{}\"", column_span));
+    }
+    if row_span > 1 {
+        result.push_str(&format!(" rowspan=\"{}\"", row_span));
+    }
+    if let Some((_, cell_style)) = lines.first() {
+        result.push_str(
+            &style_attributes(cell_style, true, style));
+    }
+    result.push('>');
+
+    let cell_appearance = lines.first()
+        .map(|(_, line_style)| appearance(line_style));
+    let contents: Vec<String> = lines.iter()
+        .map(|(line, line_style)| {
+            let text = escape(&strip_escapes(line));
+            if Some(appearance(line_style)) == cell_appearance {
+                text
+            } else {
+                format!(
+                    "<span{}>{}</span>",
+                    style_attributes(line_style, false, style),
+                    text)
+            }
+        })
+        .collect();
+    result.push_str(&contents.join("<br>"));
+
+    result.push_str(&format!("</{}>", tag));
+    result
+}
+
+/// Returns the colors and text attributes of a content style.
+fn appearance(
+    style: &ContentStyle
+) -> (Option<Color>, Option<Color>, TextAttributes) {
+    (style.foreground_color, style.background_color, style.attributes)
+}
+
+/// Returns the attributes applying a content style to an element.
+///
+/// # Arguments
+///
+/// * `content_style` - The content style.
+/// * `align` - Whether to include the alignment.
+/// * `style` - How content styles are written.
+fn style_attributes(
+    content_style: &ContentStyle,
+    align: bool,
+    style: &HtmlStyle
+) -> String {
+    let mut declarations: Vec<String> = Vec::new();
+    let mut classes: Vec<String> = Vec::new();
+
+    // Inline styles show reversed text by swapping the colors
+    let attributes = &content_style.attributes;
+    let colors =
+        (content_style.foreground_color, content_style.background_color);
+    let (foreground, background) =
+        match (style, attributes.reversed) {
+            (HtmlStyle::Inline, true) => (colors.1, colors.0),
+            _ => colors
+        };
+
+    let properties = [
+        ("color", "fg", foreground),
+        ("background-color", "bg", background)
+    ];
+    for (property, class, color) in properties.iter() {
+        if let Some(color) = color {
+            match (style, color_name(*color)) {
+                (HtmlStyle::Classes(prefix), Some(name)) =>
+                    classes.push(format!("{}{}-{}", prefix, class, name)),
+                _ =>
+                    declarations.push(
+                        format!("{}:{}", property, css_color(*color)))
+            }
+        }
+    }
+
+    match style {
+        HtmlStyle::Inline => {
+            if attributes.bold {
+                declarations.push(String::from("font-weight:bold"));
+            }
+            if attributes.italic {
+                declarations.push(String::from("font-style:italic"));
+            }
+            let decorations: Vec<&str> = [
+                (attributes.underline, "underline"),
+                (attributes.strikethrough, "line-through")
+            ].iter()
+                .filter(|(set, _)| *set)
+                .map(|(_, decoration)| *decoration)
+                .collect();
+            if !decorations.is_empty() {
+                declarations.push(
+                    format!("text-decoration:{}", decorations.join(" ")));
+            }
+            if attributes.dimmed {
+                declarations.push(String::from("opacity:0.5"));
+            }
+        },
+        HtmlStyle::Classes(prefix) => {
+            let names = [
+                (attributes.bold, "bold"),
+                (attributes.italic, "italic"),
+                (attributes.underline, "underline"),
+                (attributes.dimmed, "dimmed"),
+                (attributes.strikethrough, "strikethrough"),
+                (attributes.reversed, "reversed")
+            ];
+            for (_, name) in names.iter().filter(|(set, _)| *set) {
+                classes.push(format!("{}{}", prefix, name));
+            }
+        }
+    }
+
+    // Left alignment is the default, so is not written
+    let alignment = match content_style.alignment {
+        Alignment::Left => None,
+        Alignment::Center => Some("center"),
+        Alignment::Right => Some("right")
+    };
+    if let (true, Some(alignment)) = (align, alignment) {
+        match style {
+            HtmlStyle::Inline =>
+                declarations.push(format!("text-align:{}", alignment)),
+            HtmlStyle::Classes(prefix) =>
+                classes.push(format!("{}align-{}", prefix, alignment))
+        }
+    }
+
+    let mut result = String::from("");
+    if !classes.is_empty() {
+        result.push_str(&format!(" class=\"{}\"", classes.join(" ")));
+    }
+    if !declarations.is_empty() {
+        result.push_str(&format!(" style=\"{}\"", declarations.join(";")));
+    }
+    result
+}
+
+/// Returns the class name of a color, if it has one.
+fn color_name(color: Color) -> Option<&'static str> {
+    match color {
+        Color::Black => Some("black"),
+        Color::Red => Some("red"),
+        Color::Green => Some("green"),
+        Color::Yellow => Some("yellow"),
+        Color::Blue => Some("blue"),
+        Color::Magenta => Some("magenta"),
+        Color::Cyan => Some("cyan"),
+        Color::White => Some("white"),
+        Color::BrightBlack => Some("bright-black"),
+        Color::BrightRed => Some("bright-red"),
+        Color::BrightGreen => Some("bright-green"),
+        Color::BrightYellow => Some("bright-yellow"),
+        Color::BrightBlue => Some("bright-blue"),
+        Color::BrightMagenta => Some("bright-magenta"),
+        Color::BrightCyan => Some("bright-cyan"),
+        Color::BrightWhite => Some("bright-white"),
+        Color::TrueColor { .. } => None
+    }
+}
+
+/// Returns the CSS value of a color.
+///
+/// Terminal colors use the default xterm palette.
+fn css_color(color: Color) -> String {
+    let (r, g, b) = rgb(color);
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Escapes text for use in HTML.
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            _ => result.push(c)
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::Border;
+    use crate::table::row::Row;
+    use crate::content::CellWidth;
+
+    fn sample_table() -> Table {
+        let mut total = crate::cell!("{>}", "Total: 3 < 4");
+        total.set_column_span(2);
+        let mut fruit = crate::cell!("{}", "Fruit");
+        fruit.set_row_span(2);
+        Table::new(
+            Border::default(),
+            vec!(CellWidth::Content, CellWidth::Content),
+            crate::row!("{r^*}" => "Kind", "{}" => "Name"),
+            Vec::new(),
+            vec!(
+                Row::from(vec!(fruit, crate::cell!("{}", "Apple & Pear"))),
+                crate::row!("{}", "Plum"),
+                Row::from(vec!(total))
+            )
+        )
+    }
+
+    #[test]
+    fn html_carries_spans_and_inline_styles() {
+        assert_eq!(
+            sample_table().to_html(),
+            "<table>\n<thead>\n<tr><th style=\"color:#cd0000;font-weight:bold;text-align:center\">Kind</th><th>Name</th></tr>\n</thead>\n<tbody>\n<tr><td rowspan=\"2\">Fruit</td><td>Apple &amp; Pear</td></tr>\n<tr><td>Plum</td></tr>\n<tr><td colspan=\"2\" style=\"text-align:right\">Total: 3 &lt; 4</td></tr>\n</tbody>\n</table>\n"
+        );
+    }
+
+    #[test]
+    fn html_styles_as_css_classes() {
+        let html = sample_table()
+            .to_html_with(&HtmlStyle::Classes(String::from("tf-")));
+
+        assert!(html.contains(
+            "<th class=\"tf-fg-red tf-bold tf-align-center\">Kind</th>"));
+        assert!(html.contains(
+            "<td colspan=\"2\" class=\"tf-align-right\">Total: 3 &lt; 4</td>"));
+    }
+}
@@ -0,0 +1,335 @@
+use serde_json::{Map, Value};
+use super::Table;
+use super::cell::Cell;
+use super::row::Row;
+use crate::content::ContentStyle;
+use crate::data_item::DataItem;
+
+/// How nested objects and arrays are shown in a table built from JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NestedValues {
+    /// Nested values are shown as compact JSON in a single column.
+    Compact,
+    /// Nested values are flattened into a column per value, named by the
+    ///  dotted path to the value (such as `address.city` or `tags.0`).
+    Flatten,
+}
+
+/// Options for building a table from JSON.
+#[derive(Debug, Clone)]
+pub struct JsonOptions {
+    /// The text shown for missing keys and `null` values.
+    pub null_placeholder: String,
+    /// How nested objects and arrays are shown.
+    pub nested: NestedValues,
+}
+
+impl Default for JsonOptions {
+    fn default() -> Self {
+        JsonOptions {
+            null_placeholder: String::from(""),
+            nested: NestedValues::Compact,
+        }
+    }
+}
+
+/// An error building a table from JSON.
+#[derive(Debug)]
+pub enum JsonTableError {
+    /// The text is not valid JSON.
+    Parse(serde_json::Error),
+    /// A value could not be serialized.
+    Serialize(serde_json::Error),
+    /// The JSON value is not an array.
+    NotAnArray,
+    /// The array element at an index is not an object.
+    NotAnObject(usize),
+}
+
+impl std::fmt::Display for JsonTableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonTableError::Parse(error) =>
+                write!(f, "invalid JSON: {}", error),
+            JsonTableError::Serialize(error) =>
+                write!(f, "could not serialize value: {}", error),
+            JsonTableError::NotAnArray =>
+                write!(f, "expected a JSON array of objects"),
+            JsonTableError::NotAnObject(ix) =>
+                write!(f, "array element {} is not an object", ix),
+        }
+    }
+}
+
+impl std::error::Error for JsonTableError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsonTableError::Parse(error)
+                | JsonTableError::Serialize(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for JsonTableError {
+    fn from(error: serde_json::Error) -> Self {
+        JsonTableError::Parse(error)
+    }
+}
+
+impl Table {
+    /// Returns a table read from a JSON array of objects.
+    ///
+    /// See `from_json_value`.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader providing the JSON text.
+    /// * `options` - How null and nested values are shown.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not valid JSON or is not an array of
+    ///  objects.
+    pub fn from_json<R: std::io::Read>(
+        reader: R,
+        options: &JsonOptions
+    ) -> Result<Table, JsonTableError> {
+        let value: Value = serde_json::from_reader(reader)?;
+        Table::from_json_value(&value, options)
+    }
+
+    /// Returns a table built from a JSON array of objects.
+    ///
+    /// The keys of all of the objects form the column headers, in the order
+    ///  they are first seen. Each object becomes a row, built through
+    ///  `from_data_source`. Strings are shown without quotes and strings
+    ///  containing line breaks become multi-line cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The JSON array.
+    /// * `options` - How null and nested values are shown.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not an array of objects.
+    pub fn from_json_value(
+        value: &Value,
+        options: &JsonOptions
+    ) -> Result<Table, JsonTableError> {
+        let objects = value.as_array().ok_or(JsonTableError::NotAnArray)?;
+
+        // Flatten each object into (column, value) pairs
+        let mut rows: Vec<Map<String, Value>> = Vec::new();
+        for (ix, object) in objects.iter().enumerate() {
+            let object = object.as_object()
+                .ok_or(JsonTableError::NotAnObject(ix))?;
+            let mut row = Map::new();
+            for (key, value) in object {
+                flatten_into(&mut row, key.clone(), value, options.nested);
+            }
+            rows.push(row);
+        }
+
+        // Union the columns in first-seen order
+        let mut columns: Vec<&String> = Vec::new();
+        for row in &rows {
+            for key in row.keys() {
+                if !columns.contains(&key) {
+                    columns.push(key);
+                }
+            }
+        }
+
+        let column_headers = Row::from(
+            columns.iter()
+                .map(|column| Cell::from_data_item(
+                    &DataItem::from_text(column), ContentStyle::default()))
+                .collect()
+        );
+
+        let data = rows.iter()
+            .map(|row| columns.iter()
+                .map(|column| DataItem::from_text(
+                    &value_text(row.get(column.as_str()), options)))
+                .collect());
+
+        let table = Table::from_data_source(
+            column_headers,
+            &[],
+            Vec::new(),
+            data
+        );
+
+        Ok(table)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Table {
+    /// Returns a table built from a slice of serializable values.
+    ///
+    /// Each value must serialize as a struct or map. Field names become the
+    ///  column headers and each value becomes a row, so serde attributes
+    ///  such as `#[serde(rename)]` and `#[serde(skip)]` apply. Values are
+    ///  shown as for `from_json_value`, using the default `JsonOptions`.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The values to show.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a value cannot be serialized or does not
+    ///  serialize as a struct or map.
+    pub fn from_serialize<T: serde::Serialize>(
+        values: &[T]
+    ) -> Result<Table, JsonTableError> {
+        Table::from_serialize_with(values, &JsonOptions::default())
+    }
+
+    /// Returns a table built from a slice of serializable values.
+    ///
+    /// See `from_serialize`.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The values to show.
+    /// * `options` - How null and nested values are shown.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a value cannot be serialized or does not
+    ///  serialize as a struct or map.
+    pub fn from_serialize_with<T: serde::Serialize>(
+        values: &[T],
+        options: &JsonOptions
+    ) -> Result<Table, JsonTableError> {
+        let value = serde_json::to_value(values)
+            .map_err(JsonTableError::Serialize)?;
+        Table::from_json_value(&value, options)
+    }
+}
+
+/// Adds a value to a flattened row, flattening nested values if required.
+///
+/// # Arguments
+///
+/// * `row` - The flattened row.
+/// * `path` - The column name (dotted path) of the value.
+/// * `value` - The value.
+/// * `nested` - How nested values are shown.
+fn flatten_into(
+    row: &mut Map<String, Value>,
+    path: String,
+    value: &Value,
+    nested: NestedValues
+) {
+    match (nested, value) {
+        (NestedValues::Flatten, Value::Object(object)) if !object.is_empty() => {
+            for (key, value) in object {
+                flatten_into(row, format!("{}.{}", path, key), value, nested);
+            }
+        },
+        (NestedValues::Flatten, Value::Array(array)) if !array.is_empty() => {
+            for (ix, value) in array.iter().enumerate() {
+                flatten_into(row, format!("{}.{}", path, ix), value, nested);
+            }
+        },
+        _ => {
+            row.insert(path, value.clone());
+        }
+    }
+}
+
+/// Returns the text shown for a value.
+///
+/// # Arguments
+///
+/// * `value` - The value, if present.
+/// * `options` - The options containing the null placeholder.
+fn value_text(
+    value: Option<&Value>,
+    options: &JsonOptions
+) -> String {
+    match value {
+        None | Some(Value::Null) => options.null_placeholder.clone(),
+        Some(Value::String(text)) => text.clone(),
+        Some(value) => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PEOPLE: &str = r#"[
+        {"name": "Ann", "address": {"city": "Leeds"}, "tags": ["a", "b"]},
+        {"name": "Bob", "age": 42, "address": null}
+    ]"#;
+
+    #[test]
+    fn json_unions_keys_and_shows_nested_compactly() {
+        let table = Table::from_json(
+            PEOPLE.as_bytes(),
+            &JsonOptions {
+                null_placeholder: String::from("-"),
+                ..JsonOptions::default()
+            }
+        ).unwrap();
+
+        assert_eq!(
+            table.to_markdown(),
+            "| name | address | tags | age |\n| :--- | :--- | :--- | :--- |\n| Ann | {\"city\":\"Leeds\"} | [\"a\",\"b\"] | - |\n| Bob | - | - | 42 |\n"
+        );
+    }
+
+    #[test]
+    fn json_flattens_nested_values_with_dotted_paths() {
+        let table = Table::from_json(
+            PEOPLE.as_bytes(),
+            &JsonOptions {
+                nested: NestedValues::Flatten,
+                ..JsonOptions::default()
+            }
+        ).unwrap();
+
+        assert_eq!(
+            table.to_markdown(),
+            "| name | address.city | tags.0 | tags.1 | age | address |\n| :--- | :--- | :--- | :--- | :--- | :--- |\n| Ann | Leeds | a | b |  |  |\n| Bob |  |  |  | 42 |  |\n"
+        );
+        assert!(matches!(
+            Table::from_json("{}".as_bytes(), &JsonOptions::default()),
+            Err(JsonTableError::NotAnArray)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_uses_renamed_fields_and_skips() {
+        #[derive(serde::Serialize)]
+        struct Server {
+            #[serde(rename = "Host")]
+            host: &'static str,
+            port: u16,
+            #[serde(skip)]
+            #[allow(dead_code)]
+            password: &'static str,
+        }
+
+        let table = Table::from_serialize(&[
+            Server { host: "alpha", port: 80, password: "x" },
+            Server { host: "beta", port: 8080, password: "y" },
+        ]).unwrap();
+
+        assert_eq!(
+            table.to_markdown(),
+            "| Host | port |\n| :--- | :--- |\n| alpha | 80 |\n| beta | 8080 |\n"
+        );
+        assert!(matches!(
+            Table::from_serialize(&[1, 2]),
+            Err(JsonTableError::NotAnObject(0))
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+use super::cell::Cell;
+use super::row::span_break;
+use crate::content::CellWidth;
+
+/// Measures the natural width of each column.
+///
+/// The natural width is the width a column takes when there is no limit on
+///  the overall table width:
+///
+/// * `Fixed` columns are always their fixed width.
+/// * `Minimum` columns are as wide as their widest cell, but no narrower
+///   than their minimum width.
+/// * `Content` columns are as wide as their widest cell.
+///
+/// A cell spanning several columns that does not fit within them widens the
+///  last of its spanned columns that is not `Fixed`.
+///
+/// # Arguments
+///
+/// * `column_breaks` - The column breaks describing each column.
+/// * `column_count` - The number of columns.
+/// * `split_width` - The width of the split between two columns.
+/// * `cells` - Each cell with the column it starts in and its column span.
+pub fn measure_natural_widths<'a, I>(
+    column_breaks: &[CellWidth],
+    column_count: usize,
+    split_width: usize,
+    cells: I
+) -> Vec<usize>
+    where
+        I: Iterator<Item=(usize, usize, &'a Cell)>
+{
+    let mut widths: Vec<usize> = (0..column_count)
+        .map(|column_ix| match column_breaks.get(column_ix) {
+            Some(CellWidth::Fixed(width) | CellWidth::Minimum(width)) => *width,
+            Some(CellWidth::Content) | None => 0
+        })
+        .collect();
+
+    // Cells spanning multiple columns are fitted once all columns are sized
+    let mut spanning: Vec<(usize, usize, &Cell)> = Vec::new();
+
+    for (column_ix, span, cell) in cells {
+        if span > 1 {
+            spanning.push((column_ix, span, cell));
+            continue;
+        }
+        let column_break = column_breaks.get(column_ix)
+            .unwrap_or(&CellWidth::Content);
+        if let CellWidth::Fixed(_) = column_break {
+            continue;
+        }
+        widths[column_ix] =
+            widths[column_ix].max(cell.measure_width(column_break));
+    }
+
+    for (start, span, cell) in spanning {
+        let spanned_width: usize =
+            widths[start..start + span].iter().sum::<usize>()
+                + (span - 1) * split_width;
+        let needed = cell.measure_width(
+            &span_break(column_breaks, start, span, split_width));
+        if needed <= spanned_width {
+            continue;
+        }
+        // Widen the last spanned column that is not fixed (if any)
+        let widen = (start..start + span).rev()
+            .find(|column_ix| !matches!(
+                column_breaks.get(*column_ix),
+                Some(CellWidth::Fixed(_))
+            ));
+        if let Some(column_ix) = widen {
+            widths[column_ix] += needed - spanned_width;
+        }
+    }
+
+    widths
+}
+
+/// Shrinks column widths until their total fits within the available width.
+///
+/// The widest shrinkable column is narrowed one char at a time. `Fixed`
+///  columns are never shrunk, `Minimum` columns are never shrunk below their
+///  minimum and `Content` columns are never shrunk below one char. If the
+///  columns cannot shrink enough, they are left at their narrowest.
+///
+/// # Arguments
+///
+/// * `widths` - The column widths to shrink.
+/// * `column_breaks` - The column breaks describing each column.
+/// * `available` - The total width available for column content.
+pub fn shrink_to_fit(
+    widths: &mut [usize],
+    column_breaks: &[CellWidth],
+    available: usize
+) {
+    let mut total: usize = widths.iter().sum();
+
+    while total > available {
+        // Find the widest column that can still shrink
+        let mut widest: Option<usize> = None;
+        for (column_ix, width) in widths.iter().enumerate() {
+            let narrowest = match column_breaks.get(column_ix) {
+                Some(CellWidth::Fixed(_)) => continue,
+                Some(CellWidth::Minimum(minimum)) => (*minimum).max(1),
+                Some(CellWidth::Content) | None => 1
+            };
+            if *width > narrowest
+                && widest.is_none_or(|ix| *width > widths[ix]) {
+                widest = Some(column_ix);
+            }
+        }
+
+        match widest {
+            Some(column_ix) => {
+                widths[column_ix] -= 1;
+                total -= 1;
+            },
+            // No column can shrink any further
+            None => break
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrink_to_fit_respects_cell_widths() {
+        let column_breaks = vec!(
+            CellWidth::Fixed(10),
+            CellWidth::Minimum(6),
+            CellWidth::Content
+        );
+        let mut widths = vec!(10, 12, 20);
+
+        shrink_to_fit(&mut widths, &column_breaks, 30);
+        assert_eq!(widths, vec!(10, 10, 10));
+
+        shrink_to_fit(&mut widths, &column_breaks, 10);
+        assert_eq!(widths, vec!(10, 6, 1));
+    }
+
+    #[test]
+    fn spanning_cells_use_the_split_width() {
+        let spanning = crate::cell!("{}", "Tomatoes");
+        let cells = [
+            (0, 1, crate::cell!("{}", "ab")),
+            (1, 1, crate::cell!("{}", "cd"))
+        ];
+
+        let widths = measure_natural_widths(
+            &[CellWidth::Content, CellWidth::Content],
+            2,
+            4,
+            cells.iter()
+                .map(|(column_ix, span, cell)| (*column_ix, *span, cell))
+                .chain(std::iter::once((0, 2, &spanning)))
+        );
+
+        assert_eq!(widths, vec!(2, 2));
+    }
+}
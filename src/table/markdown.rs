@@ -0,0 +1,138 @@
+use super::Table;
+use super::cell::Cell;
+use crate::content::Alignment;
+use crate::content::text::strip_escapes;
+
+impl Table {
+    /// Returns the table formatted as a GitHub-flavored markdown table.
+    ///
+    /// The alignment row is derived from the alignment of each column
+    ///  header. Colors are stripped, pipes are escaped and the lines of
+    ///  multi-line cells are joined with `<br>`. Markdown tables have no
+    ///  spans, so column groups are omitted and positions covered by a
+    ///  spanning cell are left empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The table to format.
+    #[must_use]
+    pub fn to_markdown(self: &Table) -> String {
+        let empty = Cell::empty();
+        let grid = self.build_grid(&empty);
+        let mut result: String = String::from("");
+
+        // The column headers are the last row of the header section (markdown
+        //  tables always have a header row, so it is left empty if missing)
+        let header_rows = grid.section_rows(0);
+        let headers =
+            if header_rows.is_empty() {
+                vec!(None; grid.column_count())
+            } else {
+                grid.row(header_rows.end - 1)
+            };
+
+        result.push_str(&format_row(
+            headers.iter()
+                .map(|placed| placed.map_or_else(
+                    String::new,
+                    |(cell, _, _)| cell_text(cell)))
+        ));
+        result.push_str(&format_row(
+            headers.iter()
+                .map(|placed| alignment_marker(
+                    &placed.map_or(
+                        Alignment::Left,
+                        |(cell, _, _)| cell.alignment())
+                ).to_string())
+        ));
+
+        for row_ix in grid.section_rows(1) {
+            result.push_str(&format_row(
+                grid.row(row_ix).iter()
+                    .map(|placed| placed.map_or_else(
+                        String::new,
+                        |(cell, _, _)| cell_text(cell)))
+            ));
+        }
+
+        result
+    }
+}
+
+/// Formats a row of a markdown table.
+///
+/// # Arguments
+///
+/// * `cells` - The text of each cell in the row.
+fn format_row<I>(cells: I) -> String
+    where
+        I: Iterator<Item=String>
+{
+    let mut result = String::from("|");
+    for cell in cells {
+        result.push(' ');
+        result.push_str(&cell);
+        result.push_str(" |");
+    }
+    result.push('\n');
+    result
+}
+
+/// Returns the text of a cell for use in a markdown table.
+///
+/// # Arguments
+///
+/// * `cell` - The table cell.
+fn cell_text(cell: &Cell) -> String {
+    cell.lines()
+        .map(|line| strip_escapes(line).replace('|', "\\|"))
+        .collect::<Vec<String>>()
+        .join("<br>")
+}
+
+/// Returns the alignment row marker for a column.
+///
+/// # Arguments
+///
+/// * `alignment` - The alignment of the column header.
+fn alignment_marker(alignment: &Alignment) -> &'static str {
+    match alignment {
+        Alignment::Left => ":---",
+        Alignment::Center => ":---:",
+        Alignment::Right => "---:"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::table::{Border, Table};
+    use crate::table::row::Row;
+    use crate::table::cell::Cell;
+    use crate::content::CellWidth;
+
+    #[test]
+    fn markdown_aligns_and_escapes_cells() {
+        let table = Table::new(
+            Border::default(),
+            vec!(CellWidth::Content, CellWidth::Content, CellWidth::Content),
+            crate::row!("{<}" => "Name", "{^}" => "Notes", "{>}" => "Price"),
+            Vec::new(),
+            vec!(
+                crate::row!("{}", "Fish", "fresh | local", "$5"),
+                Row::from(vec!(
+                    crate::cell!("{}", "Steak"),
+                    Cell::from_styled_content(
+                        "{r} {}",
+                        vec!("\u{1b}[31mrare\u{1b}[0m", "medium")
+                    ),
+                    crate::cell!("{}", "$12")
+                ))
+            )
+        );
+
+        assert_eq!(
+            table.to_markdown(),
+            "| Name | Notes | Price |\n| :--- | :---: | ---: |\n| Fish | fresh \\| local | $5 |\n| Steak | rare<br>medium | $12 |\n"
+        );
+    }
+}
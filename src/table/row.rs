@@ -1,6 +1,6 @@
-use super::border::Border;
 use super::cell::Cell;
-use crate::content::{CellWidth};
+use crate::content::{CellWidth, ContentStyle};
+use crate::data_item::DataItem;
 
 pub struct CellIterator<'a> {
     cells: &'a Vec<Cell>,
@@ -21,25 +21,75 @@ impl<'a> Iterator for CellIterator<'a> {
     }
 }
 
+/// Builds a row of cells, either with a style directive for each cell
+///  (`style => content`) or with one directive shared by all cells.
+///
+/// Style directives given as string literals are checked when the code is
+///  compiled, so a malformed directive is a compile error:
+///
+/// ```
+/// use libtableformat::row;
+/// use libtableformat::table::cell::Cell;
+/// use libtableformat::table::row::Row;
+///
+/// let row = row!("{^}" => "Food", "{>:5:}" => "Count");
+/// ```
+///
+/// ```compile_fail
+/// use libtableformat::row;
+/// use libtableformat::table::cell::Cell;
+/// use libtableformat::table::row::Row;
+///
+/// let row = row!("{^}" => "Food", "{>:5}" => "Count");
+/// ```
 #[allow(unused_macros)]
 #[macro_export]
 macro_rules! row {
+    ( $($style:literal => $content:expr),* ) => {
+        {
+            let mut r: Row = Row::new();
+            $( r.add_cell($crate::cell!($style, $content)); )*
+            r
+        }
+    };
     ( $($style:expr => $content:expr),* ) => {
         {
             let mut r: Row = Row::new();
-            $( r.add_cell(crate::cell!($style, $content)); )*
+            $( r.add_cell($crate::cell!($style, $content)); )*
+            r
+        }
+    };
+    ( $style:literal, $($content:expr),* ) => {
+        {
+            let mut r: Row = Row::new();
+            $( r.add_cell($crate::cell!($style, $content)); )*
             r
         }
     };
     ( $style:expr, $($content:expr),* ) => {
         {
             let mut r: Row = Row::new();
-            $( r.add_cell(crate::cell!($style, $content)); )*
+            $( r.add_cell($crate::cell!($style, $content)); )*
             r
         }
     };
 }
 
+/// A type whose values can be shown as the rows of a table.
+///
+/// With the `derive` feature enabled this can be derived for structs with
+///  `#[derive(TableRow)]`.
+pub trait TableRow {
+    /// Returns the column headers.
+    fn header_row() -> Row;
+
+    /// Returns the base style of the cells in each column.
+    fn cell_styles() -> Vec<ContentStyle>;
+
+    /// Returns a data item for each column of this value.
+    fn data_items(&self) -> Vec<DataItem>;
+}
+
 /// Table rows represent horizontal breakpoints.
 #[derive(Debug)]
 pub struct Row {
@@ -74,7 +124,7 @@ impl Row {
     #[must_use]
     pub fn iter(
         self: &Row,
-    ) -> CellIterator {
+    ) -> CellIterator<'_> {
         CellIterator {
             cells: &self.cells,
             current_cell_ix: 0
@@ -91,104 +141,69 @@ impl Row {
         self.len() == 0
     }
 
-    /// Formats a table row.
+    /// Returns the number of columns covered by the cells of this row.
     ///
     /// # Arguments
     ///
-    /// * `self` - The table row to format.
-    /// * `border` - The table border.
-    /// * `column_breaks` - The breakpoints at which to wrap or truncate.
+    /// * `self` - The table row being measured.
     #[must_use]
-    #[allow(clippy::option_if_let_else)]
-    pub fn format(
-        self: &Row,
-        border: &Border,
-        column_breaks: &[CellWidth]
-    ) -> String {
-        let mut result: String = String::from("");
-
-        let row_height = self.measure_height(column_breaks);
-
-        // Get content iterators for each cell
-        let mut content_iterators = Vec::new();
-        for (cell_ix, cell) in self.cells.iter().enumerate() {
-            let column_break = &column_breaks[cell_ix];
-            content_iterators.push(cell.get_iterator(&column_break));
-        }
-
-        // Iterate the number of lines
-        let content_break = CellWidth::default();
-        for _line_ix in 0..row_height {
-            // Left border
-            result.push_str(&border.format_left());
-            // Write the contents for the current line of the cell
-            for cell_ix in 0..self.cells.len() {
-                let cell = &self.cells[cell_ix];
-                let column_break: &CellWidth =
-                    if cell_ix < column_breaks.len() {
-                        &column_breaks[cell_ix]
-                    } else {
-                        &content_break
-                    };
-                result.push_str(
-                    &if let Some(content) = content_iterators[cell_ix].next() {
-                        content.to_string()
-                    } else {
-                        // No more lines so fill height with empty space
-                        let cell_width = cell.measure_width(column_break);
-                        (0..cell_width)
-                            .map(|_| " ")
-                            .collect::<String>()
-                    }
-                );
-                // Vertical split (except for final column)
-                if cell_ix < column_breaks.len() - 1 {
-                    result.push_str(&border.format_vertical_split());
-                }
-            }
-            // Right border
-            result.push_str(&border.format_right());
-            result.push('\n');
-        }
-
-        result
+    pub fn column_count(self: &Row) -> usize {
+        self.cells.iter().map(Cell::column_span).sum()
     }
+}
 
-    /// Measures the height of a table row.
-    ///
-    /// # Arguments
-    ///
-    /// * `self` - The table row being measured.
-    /// * `columns` - The columns used to format the cells for this row.
-    #[must_use]
-    pub fn measure_height(
-        self: &Row,
-        column_breaks: &[CellWidth],
-    ) -> usize {
-        let mut tallest_height = 0;
-
-        // Iterate the row cells and measure based upon supplied column breaks
-        let column_break_ix = 0;
-        let content_break = CellWidth::Content;
-        for cell in &self.cells {
-            // Get the next column break (if one is available)
-            let column_break: &CellWidth = 
-                if column_break_ix < column_breaks.len() {
-                    &column_breaks[column_break_ix]
-                } else {
-                    // Use content-width break for additional columns
-                    &content_break
-                };
-            let cell_height = cell.measure_height(column_break);
-            if cell_height > tallest_height {
-                tallest_height = cell_height;
+/// Returns the column break for a cell spanning one or more columns.
+///
+/// Spanned `Fixed` or `Minimum` columns combine into a single break that
+///  includes the width of the splits between them. Columns beyond
+///  the supplied breaks (or a mix of break kinds) are sized by content.
+///
+/// # Arguments
+///
+/// * `column_breaks` - The column breaks of the table.
+/// * `start` - The first column spanned.
+/// * `span` - The number of columns spanned.
+/// * `split_width` - The width of the split between two columns.
+#[must_use]
+pub fn span_break(
+    column_breaks: &[CellWidth],
+    start: usize,
+    span: usize,
+    split_width: usize
+) -> CellWidth {
+    let spanned = column_breaks.get(start..start + span);
+    match spanned {
+        Some([column_break]) => column_break.clone(),
+        Some(spanned) => {
+            let splits = (span - 1) * split_width;
+            if let Some(widths) = fixed_widths(spanned) {
+                CellWidth::Fixed(widths + splits)
+            } else if spanned.iter()
+                .all(|column_break| matches!(column_break, CellWidth::Minimum(_))) {
+                let minimums = spanned.iter()
+                    .map(|column_break| match column_break {
+                        CellWidth::Minimum(minimum) => *minimum,
+                        _ => 0
+                    })
+                    .sum::<usize>();
+                CellWidth::Minimum(minimums + splits)
+            } else {
+                CellWidth::Content
             }
-        }
-
-        tallest_height
+        },
+        None => CellWidth::Content
     }
 }
 
+/// Returns the total width of a set of column breaks if all are fixed.
+fn fixed_widths(column_breaks: &[CellWidth]) -> Option<usize> {
+    column_breaks.iter()
+        .map(|column_break| match column_break {
+            CellWidth::Fixed(width) => Some(*width),
+            _ => None
+        })
+        .sum()
+}
 
 #[cfg(test)]
 mod tests {
@@ -1,5 +0,0 @@
-use super::content::Content;
-
-pub trait TableDataSource {
-    fn get_data_iterator() -> dyn Iterator<Item = Vec<Content>>;
-}
\ No newline at end of file
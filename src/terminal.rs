@@ -0,0 +1,137 @@
+use std::env;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicU8, Ordering};
+use terminal_size::{terminal_size_of, Width};
+
+/// The width used when the terminal width cannot be determined.
+pub const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+/// The number of colors a terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// The 16 basic ANSI colors.
+    Basic,
+    /// The 256 colors of the xterm palette.
+    Indexed,
+    /// 24-bit RGB colors.
+    TrueColor,
+}
+
+/// The color depth set with `set_color_depth` (0 when detected).
+static COLOR_DEPTH: AtomicU8 = AtomicU8::new(0);
+
+/// Returns the width of the terminal, in chars.
+///
+/// The width is taken from the `COLUMNS` environment variable if it is set
+///  to a positive number. Otherwise the terminal attached to stdout is
+///  queried for its size. If stdout is not a terminal (for example when
+///  output is piped to a file) then `DEFAULT_TERMINAL_WIDTH` is returned.
+#[must_use]
+pub fn terminal_width() -> usize {
+    if let Some(width) = columns_from_env(env::var("COLUMNS")) {
+        return width;
+    }
+
+    let stdout = io::stdout();
+    if stdout.is_terminal() {
+        if let Some((Width(width), _)) = terminal_size_of(stdout) {
+            return usize::from(width);
+        }
+    }
+
+    DEFAULT_TERMINAL_WIDTH
+}
+
+/// Parses a terminal width from the value of the `COLUMNS` variable.
+///
+/// # Arguments
+///
+/// * `columns` - The value of the `COLUMNS` environment variable.
+fn columns_from_env(
+    columns: Result<String, env::VarError>
+) -> Option<usize> {
+    columns.ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|width| *width > 0)
+}
+
+/// Returns the color depth used for 256-color and RGB colors.
+///
+/// The depth set with `set_color_depth` is used if there is one. Otherwise
+///  the depth is detected from the environment: `COLORTERM` set to
+///  `truecolor` or `24bit` gives `TrueColor`, and a `TERM` containing
+///  `256color` gives `Indexed`. Any other terminal gets `Basic` colors, so
+///  RGB colors are shown as the nearest of the 16 basic colors.
+#[must_use]
+pub fn color_depth() -> ColorDepth {
+    match COLOR_DEPTH.load(Ordering::Relaxed) {
+        1 => ColorDepth::Basic,
+        2 => ColorDepth::Indexed,
+        3 => ColorDepth::TrueColor,
+        _ => depth_from_env(env::var("COLORTERM"), env::var("TERM"))
+    }
+}
+
+/// Sets the color depth used for 256-color and RGB colors, overriding the
+///  depth detected from the environment.
+///
+/// # Arguments
+///
+/// * `depth` - The color depth, or `None` to detect it again.
+pub fn set_color_depth(
+    depth: Option<ColorDepth>
+) {
+    let value = match depth {
+        None => 0,
+        Some(ColorDepth::Basic) => 1,
+        Some(ColorDepth::Indexed) => 2,
+        Some(ColorDepth::TrueColor) => 3,
+    };
+    COLOR_DEPTH.store(value, Ordering::Relaxed);
+}
+
+/// Detects a color depth from the `COLORTERM` and `TERM` variables.
+///
+/// # Arguments
+///
+/// * `colorterm` - The value of the `COLORTERM` environment variable.
+/// * `term` - The value of the `TERM` environment variable.
+fn depth_from_env(
+    colorterm: Result<String, env::VarError>,
+    term: Result<String, env::VarError>
+) -> ColorDepth {
+    let colorterm = colorterm.unwrap_or_default();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        ColorDepth::TrueColor
+    } else if term.is_ok_and(|term| term.contains("256color")) {
+        ColorDepth::Indexed
+    } else {
+        ColorDepth::Basic
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_from_env_requires_positive_number() {
+        assert_eq!(columns_from_env(Ok("120".to_string())), Some(120));
+        assert_eq!(columns_from_env(Ok("0".to_string())), None);
+        assert_eq!(columns_from_env(Ok("wide".to_string())), None);
+        assert_eq!(columns_from_env(Err(env::VarError::NotPresent)), None);
+    }
+
+    #[test]
+    fn depth_from_env_prefers_colorterm() {
+        let unset = || Err(env::VarError::NotPresent);
+
+        assert_eq!(
+            depth_from_env(Ok("24bit".to_string()), Ok("xterm".to_string())),
+            ColorDepth::TrueColor);
+        assert_eq!(
+            depth_from_env(unset(), Ok("xterm-256color".to_string())),
+            ColorDepth::Indexed);
+        assert_eq!(depth_from_env(unset(), unset()), ColorDepth::Basic);
+    }
+}
@@ -0,0 +1,136 @@
+//! Derive macro for the `TableRow` trait of the `table-format` crate.
+//!
+//! Enable the `derive` feature of `table-format` rather than depending on
+//!  this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr, Path};
+
+/// The table options of a struct field.
+struct FieldOptions {
+    style: LitStr,
+    header: Option<String>,
+    skip: bool,
+    formatter: Option<Path>,
+}
+
+/// Derives `TableRow` for a struct with named fields.
+///
+/// Each field becomes a column, headed by the field name. Values are shown
+///  using their `Display` implementation. Fields accept a `#[table(...)]`
+///  attribute with the following options:
+///
+/// * `style = "{>:10:}"` - The style directive for the header and cells.
+/// * `header = "Name"` - The header text.
+/// * `skip` - Leaves the field out of the table.
+/// * `formatter = "path::to::function"` - A function taking a reference to
+///   the field and returning the `String` to show.
+#[proc_macro_derive(TableRow, attributes(table))]
+pub fn derive_table_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Expands the derive for a struct.
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(
+                input,
+                "TableRow can only be derived for structs with named fields"
+            )),
+        },
+        _ => return Err(syn::Error::new_spanned(
+            input,
+            "TableRow can only be derived for structs"
+        )),
+    };
+
+    let mut headers = Vec::new();
+    let mut styles = Vec::new();
+    let mut values = Vec::new();
+    for field in fields {
+        let options = field_options(field)?;
+        if options.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("named field");
+        let header = options.header
+            .unwrap_or_else(|| ident.to_string());
+        let style = options.style;
+
+        headers.push(quote! {
+            ::libtableformat::table::cell::Cell::from_styled_content(
+                #style, vec!(#header))
+        });
+        // Reject malformed directives at compile time, against the attribute
+        styles.push(quote_spanned! {style.span()=>
+            {
+                const _: () = ::libtableformat::ContentStyle
+                    ::assert_valid_directive(#style);
+                ::libtableformat::ContentStyle::from_format(#style)
+            }
+        });
+        values.push(match options.formatter {
+            Some(formatter) => quote! { #formatter(&self.#ident) },
+            None => quote! { ::std::string::ToString::to_string(&self.#ident) },
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::libtableformat::TableRow
+            for #name #type_generics #where_clause {
+            fn header_row() -> ::libtableformat::table::row::Row {
+                ::libtableformat::table::row::Row::from(vec!(#(#headers),*))
+            }
+
+            fn cell_styles() -> ::std::vec::Vec<::libtableformat::ContentStyle> {
+                vec!(#(#styles),*)
+            }
+
+            fn data_items(&self) -> ::std::vec::Vec<::libtableformat::DataItem> {
+                vec!(#(::libtableformat::DataItem::from_text(&#values)),*)
+            }
+        }
+    })
+}
+
+/// Reads the `#[table(...)]` options of a field.
+fn field_options(field: &syn::Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions {
+        style: LitStr::new("{}", Span::call_site()),
+        header: None,
+        skip: false,
+        formatter: None,
+    };
+
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("table")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("style") {
+                options.style = meta.value()?.parse::<LitStr>()?;
+            } else if meta.path.is_ident("header") {
+                options.header = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else if meta.path.is_ident("formatter") {
+                options.formatter =
+                    Some(meta.value()?.parse::<LitStr>()?.parse::<Path>()?);
+            } else {
+                return Err(meta.error(
+                    "expected `style`, `header`, `skip` or `formatter`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(options)
+}